@@ -1,5 +1,5 @@
+use crate::app::schema::{self, SAVE, Widget};
 use crate::app::state::{App, InputMode};
-use crate::utils::constants::*;
 
 use color_eyre::eyre::Result;
 use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
@@ -21,6 +21,9 @@ impl App {
     }
 
     fn handle_key_input(&mut self, key_event: KeyEvent) {
+        let widget = schema::field(self.block_index).map(|f| f.widget);
+        self.message = None;
+
         match self.input_mode {
             InputMode::Normal => match key_event.code {
                 KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -30,22 +33,27 @@ impl App {
                 // Vim keys
                 KeyCode::Char('g') => {
                     if let Some(KeyCode::Char('g')) = self.last_key {
-                        self.block_index = IDX_NAME;
+                        self.block_index = 0;
                         self.last_key = None;
                     } else {
                         self.last_key = Some(KeyCode::Char('g'));
                     }
                 }
                 KeyCode::Char('G') => {
-                    if let Some(KeyCode::Char('g')) = self.last_key {
-                        self.block_index = IDX_SAVE;
-                    }
-                    self.block_index = IDX_SAVE;
+                    self.block_index = SAVE;
                     self.last_key = None;
                 }
                 KeyCode::Char('d') => {
                     if let Some(KeyCode::Char('d')) = self.last_key {
-                        self.input[self.block_index].value_and_reset();
+                        match widget {
+                            Some(Widget::Categories) => self.categories.clear(),
+                            Some(Widget::Actions) => self.actions.clear(),
+                            Some(w) if w.is_text() => {
+                                self.input[self.block_index].reset();
+                                self.field_changed();
+                            }
+                            _ => {}
+                        }
                         self.last_key = None;
                     } else {
                         self.last_key = Some(KeyCode::Char('d'));
@@ -64,102 +72,159 @@ impl App {
                     self.exit();
                 }
 
-                KeyCode::Char('i') => match self.block_index {
-                    IDX_TERMINAL | IDX_NODISPLAY | IDX_STARTUPNOTIFY => {
+                KeyCode::Char('t') => {
+                    if let Some(key) = self.localized_key() {
+                        self.open_translations(key);
+                        self.input_mode = InputMode::Insert;
+                    }
+                }
+
+                KeyCode::Char('i') => match widget {
+                    Some(Widget::Checkbox) => {
                         self.checkbox();
                         self.next_block();
                     }
-                    IDX_TYPE => {
-                        self.open_dropdown(
-                            IDX_TYPE,
-                            vec!["Application", "Application (other)", "Link", "Directory"],
-                        );
+                    Some(Widget::Dropdown) => {
+                        self.open_dropdown(self.block_index);
                         self.input_mode = InputMode::Insert;
                     }
-
-                    IDX_CATEGORY => {
-                        self.open_dropdown(
-                            IDX_CATEGORY,
-                            vec![
-                                "None",
-                                "Audio",
-                                "Video",
-                                "Development",
-                                "Education",
-                                "Graphics",
-                                "Network",
-                                "Office",
-                                "Settings",
-                                "System",
-                            ],
-                        );
+                    Some(Widget::Actions) => {
+                        self.open_actions();
                         self.input_mode = InputMode::Insert;
                     }
-                    IDX_SAVE => {
-                        // if !self.can_save() {
-                        //     return;
-                        // }
-                        let file_name = format!("{}.desktop", self.input[IDX_NAME].value());
-                        self.save_as_desktop(&file_name)
-                            .expect("[ERROR]: Can't save!");
-                        self.exit();
+                    Some(Widget::Categories) => {
+                        self.open_picker();
+                        self.input_mode = InputMode::Insert;
+                    }
+                    Some(_) => self.input_mode = InputMode::Insert,
+                    None if self.block_index == SAVE => {
+                        // The ID field or the status line shows why it
+                        // wasn't saved.
+                        if self.save() {
+                            self.exit();
+                        }
                     }
-                    IDX_CANCEL => self.exit(),
-                    _ => self.input_mode = InputMode::Insert,
+                    None => self.exit(),
                 },
 
                 _ => self.last_key = None,
             },
 
-            InputMode::Insert => {
-                if self.block_index != IDX_TYPE && self.block_index != IDX_CATEGORY {
-                    self.input[self.block_index].handle_event(&Event::Key(key_event));
-                }
-
-                // tHIS IS A PIECE OF SHIT, HELP M,E
-                if let Some(idx) = self.dropdown_index {
-                    if key_event.code == KeyCode::Down || key_event.code == KeyCode::Char('j') {
-                        self.dropdown_selected =
-                            (self.dropdown_selected + 1) % self.dropdown_options.len();
-                        self.input[idx] =
-                            Input::from(self.dropdown_options[self.dropdown_selected]);
-                    }
-                    if key_event.code == KeyCode::Up || key_event.code == KeyCode::Char('k') {
-                        self.dropdown_selected = self.dropdown_selected.saturating_sub(1);
-                        self.input[idx] =
-                            Input::from(self.dropdown_options[self.dropdown_selected]);
-                    }
-                    if key_event.code == KeyCode::Enter || key_event.code == KeyCode::Char('i') {
-                        self.dropdown_open = false;
-                        self.dropdown_index = None;
-                        self.submit_message();
+            InputMode::Insert if self.picker_open => self.handle_picker_key(key_event),
+            InputMode::Insert if self.actions_open => self.handle_actions_key(key_event),
+            InputMode::Insert if self.translations_open => self.handle_translations_key(key_event),
+            InputMode::Insert if self.dropdown_open => self.handle_dropdown_key(key_event),
+
+            InputMode::Insert => match key_event.code {
+                KeyCode::Esc => self.input_mode = InputMode::Normal,
+                KeyCode::Enter => {
+                    self.submit_message();
+                    // Only text fields are typed into.
+                    if !schema::field(self.block_index).is_some_and(|f| f.widget.is_text()) {
                         self.input_mode = InputMode::Normal;
                     }
                 }
-
-                if key_event.code == KeyCode::Esc {
-                    match self.block_index {
-                        IDX_TYPE | IDX_CATEGORY => {
-                            self.dropdown_open = false;
-                            self.dropdown_index = None;
-                            self.input_mode = InputMode::Normal;
-                        }
-                        _ => self.input_mode = InputMode::Normal,
+                _ => {
+                    if widget.is_some_and(|w| w.is_text())
+                        && let Some(changed) =
+                            self.input[self.block_index].handle_event(&Event::Key(key_event))
+                        && changed.value
+                    {
+                        self.field_changed();
                     }
                 }
+            },
+        }
+    }
+    fn handle_dropdown_key(&mut self, key_event: KeyEvent) {
+        let Some(idx) = self.dropdown_index else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.dropdown_selected = (self.dropdown_selected + 1) % self.dropdown_options.len();
+                self.input[idx] = Input::from(self.dropdown_options[self.dropdown_selected]);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.dropdown_selected = self.dropdown_selected.saturating_sub(1);
+                self.input[idx] = Input::from(self.dropdown_options[self.dropdown_selected]);
+            }
+            KeyCode::Enter | KeyCode::Char('i') => {
+                self.dropdown_open = false;
+                self.dropdown_index = None;
+                self.submit_message();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Esc => {
+                self.dropdown_open = false;
+                self.dropdown_index = None;
+                self.input_mode = InputMode::Normal;
+            }
+            _ => {}
+        }
+    }
+    fn handle_picker_key(&mut self, key_event: KeyEvent) {
+        let len = self.picker_options().len();
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Enter => {
+                self.picker_open = false;
+                self.input_mode = InputMode::Normal;
+                if key_event.code == KeyCode::Enter {
+                    self.submit_message();
+                }
+            }
+            KeyCode::Down | KeyCode::Tab if len > 0 => {
+                self.picker_cursor = (self.picker_cursor + 1) % len;
+            }
+            KeyCode::Up | KeyCode::BackTab if len > 0 => {
+                self.picker_cursor = (self.picker_cursor + len - 1) % len;
+            }
+            KeyCode::Char(' ') => self.toggle_category(),
+            _ => {
+                self.picker_query.handle_event(&Event::Key(key_event));
+                self.picker_cursor = 0;
+            }
+        }
+    }
+    fn handle_actions_key(&mut self, key_event: KeyEvent) {
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
 
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Enter => {
+                self.close_actions();
+                self.input_mode = InputMode::Normal;
                 if key_event.code == KeyCode::Enter {
-                    match self.block_index {
-                        IDX_COMMENT => {
-                            self.submit_message();
-                            self.input_mode = InputMode::Normal;
-                        }
-                        IDX_TYPE => return,
-                        IDX_CATEGORY => return,
-                        _ => self.submit_message(),
-                    }
+                    self.submit_message();
                 }
             }
+            KeyCode::Char('n') if ctrl => self.add_action(),
+            KeyCode::Char('d') if ctrl => self.delete_action(),
+            KeyCode::Tab => self.move_action_field(true),
+            KeyCode::BackTab => self.move_action_field(false),
+            KeyCode::Down => self.select_action(true),
+            KeyCode::Up => self.select_action(false),
+            _ => {
+                self.action_input.handle_event(&Event::Key(key_event));
+            }
+        }
+    }
+    fn handle_translations_key(&mut self, key_event: KeyEvent) {
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Enter => {
+                self.close_translations();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Char('n') if ctrl => self.add_translation(),
+            KeyCode::Char('d') if ctrl => self.delete_translation(),
+            KeyCode::Tab | KeyCode::BackTab => self.move_translation_field(),
+            KeyCode::Down => self.select_translation(true),
+            KeyCode::Up => self.select_translation(false),
+            _ => {
+                self.translation_input.handle_event(&Event::Key(key_event));
+            }
         }
     }
 }
@@ -1,5 +1,6 @@
-pub mod state;
 pub mod event;
+pub mod schema;
+pub mod state;
 pub mod ui;
 
-pub use state::App;
\ No newline at end of file
+pub use state::App;
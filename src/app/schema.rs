@@ -0,0 +1,281 @@
+use crate::app::state::App;
+
+use ratatui::style::Style;
+
+/// How a field is drawn next to its label: its style and status text.
+pub type Status = (Style, String);
+
+/// Checks the raw text of a focused field.
+pub type Validator = fn(&App, &str) -> Status;
+
+/// The desktop file ID, which is the file name rather than a key.
+pub const ID: &str = "ID";
+
+/// Value types of desktop-entry-spec 4, as the form edits them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    String,
+    LocaleString,
+    Boolean,
+    /// `;` separated, typed as in the file.
+    List,
+    LocaleList,
+    /// One of a fixed set of strings, picked from a dropdown.
+    Enum(&'static [&'static str]),
+}
+
+impl ValueKind {
+    /// Whether the key takes `Key[locale]` translations.
+    pub fn is_localized(self) -> bool {
+        matches!(self, ValueKind::LocaleString | ValueKind::LocaleList)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Widget {
+    /// A bordered text box in the Required section.
+    Input,
+    /// A text box that grows to show all of a long value while focused.
+    Multiline,
+    /// A `Key: [ value ]` line in the Optional section.
+    Row,
+    Checkbox,
+    Dropdown,
+    /// Opens the categories picker.
+    Categories,
+    /// Opens the Desktop Actions editor.
+    Actions,
+}
+
+impl Widget {
+    /// Whether the field is typed into in insert mode.
+    pub fn is_text(self) -> bool {
+        matches!(self, Widget::Input | Widget::Multiline | Widget::Row)
+    }
+
+    /// Whether the field is drawn in the Required section.
+    pub fn is_boxed(self) -> bool {
+        matches!(self, Widget::Input | Widget::Multiline)
+    }
+}
+
+/// One field of the form.
+#[derive(Debug, Clone, Copy)]
+pub struct Field {
+    /// Key in `[Desktop Entry]`, or [`ID`].
+    pub key: &'static str,
+    pub label: &'static str,
+    pub kind: ValueKind,
+    pub widget: Widget,
+    /// Types the field is shown and saved for, every type when empty.
+    pub types: &'static [&'static str],
+    /// Types that need a value, shown as " - Empty" until there is one.
+    pub required_for: &'static [&'static str],
+    /// Value of a new launcher, and of a key missing from the file.
+    pub default: &'static str,
+    pub validate: Option<Validator>,
+}
+
+impl Field {
+    pub fn shown_for(&self, entry_type: &str) -> bool {
+        self.types.is_empty() || self.types.contains(&entry_type)
+    }
+
+    /// Whether the value is written as a key of the same name.
+    pub fn is_key(&self) -> bool {
+        self.key != ID && self.widget != Widget::Actions
+    }
+}
+
+const APPLICATION: &[&str] = &["Application"];
+const EVERY_TYPE: &[&str] = &["Application", "Link", "Directory"];
+
+pub const TYPE_OPTIONS: &[&str] = &["Application", "Application (other)", "Link", "Directory"];
+
+const ROW: Field = Field {
+    key: "",
+    label: "",
+    kind: ValueKind::String,
+    widget: Widget::Row,
+    types: &[],
+    required_for: &[],
+    default: "",
+    validate: None,
+};
+
+const CHECKBOX: Field = Field {
+    kind: ValueKind::Boolean,
+    widget: Widget::Checkbox,
+    default: "false",
+    ..ROW
+};
+
+/// The form from top to bottom. Save and Cancel follow the last field.
+pub const FIELDS: &[Field] = &[
+    Field {
+        key: "Name",
+        label: "Name",
+        kind: ValueKind::LocaleString,
+        widget: Widget::Input,
+        required_for: EVERY_TYPE,
+        ..ROW
+    },
+    Field {
+        key: ID,
+        label: "ID (file name)",
+        widget: Widget::Input,
+        required_for: EVERY_TYPE,
+        validate: Some(App::validate_name),
+        ..ROW
+    },
+    Field {
+        key: "Exec",
+        label: "Exec",
+        widget: Widget::Multiline,
+        types: APPLICATION,
+        required_for: APPLICATION,
+        validate: Some(App::validate_exec),
+        ..ROW
+    },
+    Field {
+        key: "URL",
+        label: "URL",
+        widget: Widget::Input,
+        types: &["Link"],
+        required_for: &["Link"],
+        validate: Some(App::validate_url),
+        ..ROW
+    },
+    Field {
+        key: "Icon",
+        label: "Icon",
+        widget: Widget::Input,
+        required_for: APPLICATION,
+        validate: Some(App::validate_icon),
+        ..ROW
+    },
+    Field {
+        key: "Version",
+        label: "Version",
+        validate: Some(App::validate_version),
+        ..ROW
+    },
+    Field {
+        key: "GenericName",
+        label: "GenericName",
+        kind: ValueKind::LocaleString,
+        ..ROW
+    },
+    Field {
+        key: "Comment",
+        label: "Comment",
+        kind: ValueKind::LocaleString,
+        ..ROW
+    },
+    Field {
+        key: "Keywords",
+        label: "Keywords",
+        kind: ValueKind::LocaleList,
+        types: APPLICATION,
+        validate: Some(App::validate_keywords),
+        ..ROW
+    },
+    Field {
+        key: "TryExec",
+        label: "TryExec",
+        types: APPLICATION,
+        validate: Some(App::validate_try_exec),
+        ..ROW
+    },
+    Field {
+        key: "Path",
+        label: "Path",
+        types: APPLICATION,
+        validate: Some(App::validate_working_dir),
+        ..ROW
+    },
+    Field {
+        key: "StartupWMClass",
+        label: "StartupWMClass",
+        types: APPLICATION,
+        ..ROW
+    },
+    Field {
+        key: "MimeType",
+        label: "MimeType",
+        kind: ValueKind::List,
+        types: APPLICATION,
+        validate: Some(App::validate_mime_types),
+        ..ROW
+    },
+    Field {
+        key: "Actions",
+        label: "Actions",
+        kind: ValueKind::List,
+        widget: Widget::Actions,
+        types: APPLICATION,
+        ..ROW
+    },
+    Field {
+        key: "NoDisplay",
+        label: "NoDisplay",
+        ..CHECKBOX
+    },
+    Field {
+        key: "StartupNotify",
+        label: "StartUpNotify",
+        types: APPLICATION,
+        default: "true",
+        ..CHECKBOX
+    },
+    Field {
+        key: "Terminal",
+        label: "Terminal",
+        types: APPLICATION,
+        ..CHECKBOX
+    },
+    Field {
+        key: "Type",
+        label: "Type",
+        kind: ValueKind::Enum(TYPE_OPTIONS),
+        widget: Widget::Dropdown,
+        required_for: EVERY_TYPE,
+        default: "Application",
+        ..ROW
+    },
+    Field {
+        key: "Categories",
+        label: "Categories",
+        kind: ValueKind::List,
+        widget: Widget::Categories,
+        types: APPLICATION,
+        ..ROW
+    },
+];
+
+/// Block index of the Save button.
+pub const SAVE: usize = FIELDS.len();
+/// Block index of the Cancel button, the last one.
+pub const CANCEL: usize = SAVE + 1;
+
+/// Block index of the field editing `key`.
+pub fn index(key: &str) -> usize {
+    FIELDS
+        .iter()
+        .position(|f| f.key == key)
+        .unwrap_or_else(|| panic!("no field for {key}"))
+}
+
+/// The field at a block index, `None` for the buttons.
+pub fn field(index: usize) -> Option<&'static Field> {
+    FIELDS.get(index)
+}
+
+/// Keys the translations panel edits.
+pub fn localized_keys() -> Vec<&'static str> {
+    FIELDS
+        .iter()
+        .filter(|f| f.kind.is_localized())
+        .map(|f| f.key)
+        .collect()
+}
@@ -1,26 +1,32 @@
+use crate::app::schema::{self, CANCEL, FIELDS, ID, Status, ValueKind, Widget};
+use crate::commands::rename;
 use crate::utils::constants::*;
 
+use deskforge::atomic;
+use deskforge::desktop::actions::{self, Action};
+use deskforge::desktop::exec::{self, Resolved};
+use deskforge::desktop::lint::{self, Diagnostic, Severity};
+use deskforge::desktop::locale::{self, Translation};
+use deskforge::desktop::value::{escape, split_list, unescape};
+use deskforge::desktop::{DesktopEntry, Value, categories, id};
+use deskforge::icons::{self, IconResolver};
+
 use color_eyre::eyre::Ok;
 use color_eyre::eyre::Result;
-use is_executable::IsExecutable;
 use ratatui::{
     crossterm::event::KeyCode,
     style::{Color, Style},
 };
 use tui_input::Input;
 
-use std::fs::File;
-use std::io::BufRead;
-use std::io::BufReader;
-use std::{
-    fs::OpenOptions,
-    io::Write,
-    path::{Path, PathBuf},
-};
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub struct App {
     pub input_mode: InputMode,
+    /// Raw text of every field, as written after `=`.
     pub input: Vec<Input>,
     pub last_key: Option<KeyCode>,
 
@@ -29,12 +35,53 @@ pub struct App {
     pub dropdown_selected: usize,
     pub dropdown_index: Option<usize>,
 
+    /// Position in `schema::FIELDS`, then the Save and Cancel buttons.
     pub block_index: usize,
-
-    pub checkbox_nodisplay: bool,
-    pub checkbox_startupnotify: bool,
-    pub checkbox_terminal: bool,
-
+    /// First line of the form shown when it is taller than the terminal.
+    pub scroll: u16,
+
+    pub categories: Vec<String>,
+    pub picker_open: bool,
+    pub picker_query: Input,
+    pub picker_cursor: usize,
+
+    pub actions: Vec<Action>,
+    pub actions_snapshot: Vec<Action>,
+    pub actions_open: bool,
+    pub action_selected: usize,
+    pub action_field: usize,
+    pub action_input: Input,
+
+    pub translations: Vec<Translation>,
+    pub translations_snapshot: Vec<Translation>,
+    pub translations_open: bool,
+    /// Key whose translations the panel shows.
+    pub translation_key: &'static str,
+    /// Position among the translations of `translation_key`.
+    pub translation_selected: usize,
+    pub translation_field: usize,
+    pub translation_input: Input,
+    /// Locale new translations start with, from `$LANG`.
+    pub locale: Option<String>,
+
+    /// The file being edited. Saving applies the form on top of it so keys,
+    /// groups and comments the form doesn't know about survive.
+    pub document: DesktopEntry,
+    /// The file as it was read, which the preview marks changes against.
+    pub disk: Option<String>,
+    /// Form values right after loading, used to tell which fields changed.
+    pub snapshot: Vec<(&'static str, Value)>,
+    pub icons: IconResolver,
+
+    /// File the editor saves to when editing an existing launcher.
+    pub file_name: Option<String>,
+    /// Whether the ID was given or typed in. Until then it follows Name.
+    pub id_edited: bool,
+    /// Shown below the form, like why the last save failed.
+    pub message: Option<String>,
+    /// What still uses the old ID after saving renamed the launcher, for
+    /// whoever opened the editor to show once it closes.
+    pub warnings: Vec<String>,
     pub edit: bool,
     pub exit: bool,
 }
@@ -46,62 +93,60 @@ pub enum InputMode {
 }
 
 impl App {
-    pub fn new(file_name: Option<String>, file_edit: bool) -> Self {
-        let mut input = vec![Input::default(); NUM_BLOCK];
-        let mut block_index: usize = 0;
-        let mut edit = false;
+    pub fn new(file_name: Option<String>) -> Self {
+        Self::load(file_name, None)
+    }
+
+    /// Edits the launcher at `path`, saving it as `id` in the user's
+    /// applications directory. For a system launcher that is an override.
+    /// Fails when the file can't be read or parsed, rather than starting
+    /// from an empty launcher that saving would write over it.
+    pub fn open(path: &Path, id: &str) -> Result<Self> {
+        let content = read_to_string(path)?;
+        let mut entry = DesktopEntry::parse(&content)?;
+        categories::migrate_legacy(&mut entry);
+        Ok(Self::load(Some(id.to_string()), Some((entry, content))))
+    }
 
-        input[IDX_TYPE] = Input::from("Application");
-        input[IDX_CATEGORY] = Input::from("None");
+    /// Builds the form, from `file` and its text on disk when editing.
+    fn load(file_name: Option<String>, file: Option<(DesktopEntry, String)>) -> Self {
+        let mut input: Vec<Input> = FIELDS.iter().map(|f| Input::from(f.default)).collect();
+        let mut block_index: usize = 0;
+        let edit = file.is_some();
 
         if let Some(name) = file_name.clone()
-            && name != ""
+            && !name.is_empty()
         {
-            input[0] = Input::from(name);
-            block_index += 1;
-        }
-
-        if file_edit {
-            edit = true;
-            if let Some(name) = &file_name {
-                let path: PathBuf = dirs::data_dir()
-                    .unwrap_or_else(|| PathBuf::from("/tmp"))
-                    .join("applications")
-                    .join(name);
-
-                if path.exists() {
-                    if let std::result::Result::Ok(file) = File::open(&path) {
-                        let reader = BufReader::new(file);
-
-                        for line in reader.lines().flatten() {
-                            if let Some((key, value)) = line.split_once('=') {
-                                match key {
-                                    "Name" => input[IDX_NAME] = Input::from(value),
-                                    "Exec" => input[IDX_EXEC] = Input::from(value),
-                                    "URL" => input[IDX_URL] = Input::from(value),
-                                    "Icon" => input[IDX_ICON] = Input::from(value),
-                                    "Version" => input[IDX_VERSION] = Input::from(value),
-                                    "Comment" => input[IDX_COMMENT] = Input::from(value),
-                                    "Actions" => input[IDX_ACTION] = Input::from(value),
-                                    "NoDisplay" => input[IDX_NODISPLAY] = Input::from(value),
-                                    "StartupNotify" => {
-                                        input[IDX_STARTUPNOTIFY] = Input::from(value)
-                                    }
-                                    "Terminal" => input[IDX_TERMINAL] = Input::from(value),
-                                    "Type" => input[IDX_TYPE] = Input::from(value),
-                                    "Category" => input[IDX_CATEGORY] = Input::from(value),
-
-                                    _ => {}
-                                }
-                            }
-                        }
-                    }
-                }
+            input[schema::index(ID)] = Input::from(if edit {
+                name.clone()
+            } else {
+                id::from_name(&name)
+            });
+            input[schema::index("Name")] = Input::from(name);
+            block_index = schema::index("Exec");
+        }
+
+        let (entry, disk) = match file {
+            Some((entry, content)) => (entry, Some(content)),
+            None => (DesktopEntry::new(), None),
+        };
+
+        for (index, field) in FIELDS.iter().enumerate() {
+            if field.is_key()
+                && field.widget != Widget::Categories
+                && let Some(value) = entry.get(field.key)
+            {
+                input[index] = Input::from(value.to_raw());
             }
         }
 
-        Self {
+        let categories = entry.get_list("Categories").unwrap_or_default().to_vec();
+        let loaded_actions = actions::read(&entry);
+        let loaded_translations = locale::read(&entry, &schema::localized_keys());
+
+        let mut app = Self {
             block_index,
+            scroll: 0,
             input_mode: InputMode::Normal,
             input,
 
@@ -112,317 +157,722 @@ impl App {
 
             last_key: None,
 
-            checkbox_nodisplay: false,
-            checkbox_startupnotify: true,
-            checkbox_terminal: false,
-
+            categories,
+            picker_open: false,
+            picker_query: Input::default(),
+            picker_cursor: 0,
+
+            actions_snapshot: loaded_actions.clone(),
+            actions: loaded_actions,
+            actions_open: false,
+            action_selected: 0,
+            action_field: 0,
+            action_input: Input::default(),
+
+            translations_snapshot: loaded_translations.clone(),
+            translations: loaded_translations,
+            translations_open: false,
+            translation_key: "Name",
+            translation_selected: 0,
+            translation_field: 0,
+            translation_input: Input::default(),
+            locale: locale::current(),
+
+            document: entry,
+            disk,
+            snapshot: Vec::new(),
+            icons: IconResolver::new(),
+
+            id_edited: edit || file_name.as_ref().is_some_and(|n| !n.is_empty()),
+            file_name: file_name.filter(|_| edit),
+            message: None,
+            warnings: Vec::new(),
             edit,
             exit: false,
+        };
+        // A link starts on URL rather than the hidden Exec field.
+        if !app.is_shown(app.block_index) {
+            app.next_block();
         }
+        app.snapshot = app.form_values();
+        app
+    }
+
+    /// Raw text of the field editing `key`.
+    pub fn value(&self, key: &str) -> &str {
+        self.input[schema::index(key)].value()
+    }
+
+    pub fn set_value(&mut self, key: &str, value: &str) {
+        self.input[schema::index(key)] = Input::from(value);
+    }
+
+    /// Whether the block at `index` is part of the form for the current
+    /// Type. The buttons always are.
+    pub fn is_shown(&self, index: usize) -> bool {
+        schema::field(index).is_none_or(|f| f.shown_for(self.entry_type()))
     }
 
     pub fn next_block(&mut self) {
-        if self.block_index == IDX_CANCEL {
-            return;
+        if let Some(next) = (self.block_index + 1..=CANCEL).find(|&i| self.is_shown(i)) {
+            self.block_index = next;
         }
-        self.block_index += 1;
     }
 
     pub fn previous_block(&mut self) {
-        if self.block_index == IDX_NAME {
-            return;
+        if let Some(previous) = (0..self.block_index).rev().find(|&i| self.is_shown(i)) {
+            self.block_index = previous;
         }
-        self.block_index -= 1;
     }
 
     pub fn submit_message(&mut self) {
         self.next_block();
     }
 
-    pub fn open_dropdown(&mut self, index: usize, options: Vec<&'static str>) {
+    /// Opens the options of the Enum field at `index`.
+    pub fn open_dropdown(&mut self, index: usize) {
+        let Some(ValueKind::Enum(options)) = schema::field(index).map(|f| f.kind) else {
+            return;
+        };
         self.dropdown_open = true;
-        self.dropdown_options = options;
+        self.dropdown_options = options.to_vec();
         self.dropdown_selected = 0;
         self.dropdown_index = Some(index);
         self.input[index] = Input::from(self.dropdown_options[0]);
     }
 
-    pub fn save_as_desktop(&self, file_name: &str) -> Result<()> {
-        let mut path = dirs::data_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+    pub fn open_picker(&mut self) {
+        self.picker_open = true;
+        self.picker_query.reset();
+        self.picker_cursor = 0;
+    }
+
+    /// Registered categories matching the search query.
+    pub fn picker_options(&self) -> Vec<&'static str> {
+        let query = self.picker_query.value().trim().to_lowercase();
+        categories::all()
+            .filter(|c| c.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    pub fn toggle_category(&mut self) {
+        let Some(category) = self.picker_options().get(self.picker_cursor).copied() else {
+            return;
+        };
+
+        match self.categories.iter().position(|c| c == category) {
+            Some(idx) => {
+                self.categories.remove(idx);
+            }
+            None => self.categories.push(category.to_string()),
+        }
+    }
+
+    pub fn open_actions(&mut self) {
+        if self.actions.is_empty() {
+            self.actions.push(Action::default());
+        }
+        self.actions_open = true;
+        self.action_selected = 0;
+        self.action_field = 0;
+        self.load_action_input();
+    }
+
+    /// Stores the field being edited and drops actions left completely empty.
+    pub fn close_actions(&mut self) {
+        self.commit_action_input();
+        self.actions.retain(|a| *a != Action::default());
+        self.actions_open = false;
+    }
+
+    fn action_field_mut(&mut self) -> Option<&mut String> {
+        let action = self.actions.get_mut(self.action_selected)?;
+        Some(match self.action_field {
+            0 => &mut action.id,
+            1 => &mut action.name,
+            2 => &mut action.exec,
+            _ => &mut action.icon,
+        })
+    }
+
+    pub fn load_action_input(&mut self) {
+        let value = self
+            .action_field_mut()
+            .map(|f| escape(f))
+            .unwrap_or_default();
+        self.action_input = Input::from(value);
+    }
+
+    pub fn commit_action_input(&mut self) {
+        let value = unescape(self.action_input.value());
+        if let Some(field) = self.action_field_mut() {
+            *field = value;
+        }
+    }
+
+    pub fn move_action_field(&mut self, forward: bool) {
+        self.commit_action_input();
+        let len = ACTION_FIELDS.len();
+        self.action_field = if forward {
+            (self.action_field + 1) % len
+        } else {
+            (self.action_field + len - 1) % len
+        };
+        self.load_action_input();
+    }
+
+    pub fn select_action(&mut self, forward: bool) {
+        if self.actions.is_empty() {
+            return;
+        }
+        self.commit_action_input();
+        let len = self.actions.len();
+        self.action_selected = if forward {
+            (self.action_selected + 1) % len
+        } else {
+            (self.action_selected + len - 1) % len
+        };
+        self.load_action_input();
+    }
+
+    pub fn add_action(&mut self) {
+        self.commit_action_input();
+        self.actions.push(Action::default());
+        self.action_selected = self.actions.len() - 1;
+        self.action_field = 0;
+        self.load_action_input();
+    }
+
+    pub fn delete_action(&mut self) {
+        if self.action_selected < self.actions.len() {
+            self.actions.remove(self.action_selected);
+        }
+        if self.actions.is_empty() {
+            self.actions.push(Action::default());
+        }
+        self.action_selected = self.action_selected.min(self.actions.len() - 1);
+        self.load_action_input();
+    }
 
-        path.push("applications");
-        path.push(file_name);
+    /// The key translated in the focused field, if it has translations.
+    pub fn localized_key(&self) -> Option<&'static str> {
+        schema::field(self.block_index)
+            .filter(|f| f.kind.is_localized())
+            .map(|f| f.key)
+    }
 
-        let mut file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(&path)?;
+    /// Locales `key` is translated to.
+    pub fn translated_locales(&self, key: &str) -> Vec<&str> {
+        self.translations
+            .iter()
+            .filter(|t| t.key == key && !t.locale.is_empty())
+            .map(|t| t.locale.as_str())
+            .collect()
+    }
 
-        writeln!(file, "[Desktop Entry]")?;
-        writeln!(file, "Name={}", self.input[IDX_NAME])?;
+    /// Indices into `translations` of the ones the panel shows.
+    pub fn panel_translations(&self) -> Vec<usize> {
+        self.translations
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.key == self.translation_key)
+            .map(|(i, _)| i)
+            .collect()
+    }
 
-        match self.input[IDX_TYPE].value() {
-            "Link" => writeln!(file, "URL={}", self.input[IDX_URL])?,
-            "Application" => writeln!(file, "Exec={}", self.input[IDX_EXEC])?,
-            "Directory" => writeln!(file, "Exec={}", self.input[IDX_EXEC])?,
-            "Application (other)" => writeln!(file, "Exec={}", self.input[IDX_EXEC])?,
+    pub fn open_translations(&mut self, key: &'static str) {
+        self.translation_key = key;
+        if self.panel_translations().is_empty() {
+            self.push_translation();
+        }
+        self.translations_open = true;
+        self.translation_selected = 0;
+        self.translation_field = 0;
+        self.load_translation_input();
+    }
+
+    /// Stores the field being edited and drops translations left empty.
+    pub fn close_translations(&mut self) {
+        self.commit_translation_input();
+        self.translations
+            .retain(|t| !(t.locale.is_empty() && t.value.is_empty()));
+        self.translations_open = false;
+    }
+
+    /// Adds a translation to the user's locale, unless there already is one.
+    fn push_translation(&mut self) {
+        let locale = self
+            .locale
+            .clone()
+            .filter(|l| {
+                !self
+                    .translated_locales(self.translation_key)
+                    .contains(&l.as_str())
+            })
+            .unwrap_or_default();
+        self.translations.push(Translation {
+            key: self.translation_key.to_string(),
+            locale,
+            value: String::new(),
+        });
+    }
+
+    fn translation_field_mut(&mut self) -> Option<&mut String> {
+        let index = *self.panel_translations().get(self.translation_selected)?;
+        let translation = &mut self.translations[index];
+        Some(match self.translation_field {
+            0 => &mut translation.locale,
+            _ => &mut translation.value,
+        })
+    }
+
+    pub fn load_translation_input(&mut self) {
+        let value = self.translation_field_mut().map(|f| f.clone());
+        self.translation_input = Input::from(value.unwrap_or_default());
+    }
+
+    pub fn commit_translation_input(&mut self) {
+        let value = match self.translation_field {
+            0 => self.translation_input.value().trim().to_string(),
+            _ => self.translation_input.value().to_string(),
+        };
+        if let Some(field) = self.translation_field_mut() {
+            *field = value;
+        }
+    }
+
+    pub fn move_translation_field(&mut self) {
+        self.commit_translation_input();
+        self.translation_field = (self.translation_field + 1) % TRANSLATION_FIELDS.len();
+        self.load_translation_input();
+    }
+
+    pub fn select_translation(&mut self, forward: bool) {
+        let len = self.panel_translations().len();
+        if len == 0 {
+            return;
+        }
+        self.commit_translation_input();
+        self.translation_selected = if forward {
+            (self.translation_selected + 1) % len
+        } else {
+            (self.translation_selected + len - 1) % len
+        };
+        self.load_translation_input();
+    }
+
+    pub fn add_translation(&mut self) {
+        self.commit_translation_input();
+        self.push_translation();
+        self.translation_selected = self.panel_translations().len() - 1;
+        // With the locale filled in, only the value is left to type.
+        self.translation_field = match self.translations.last() {
+            Some(t) if !t.locale.is_empty() => 1,
+            _ => 0,
+        };
+        self.load_translation_input();
+    }
+
+    pub fn delete_translation(&mut self) {
+        if let Some(&index) = self.panel_translations().get(self.translation_selected) {
+            self.translations.remove(index);
+        }
+        if self.panel_translations().is_empty() {
+            self.push_translation();
+        }
+        self.translation_selected = self
+            .translation_selected
+            .min(self.panel_translations().len() - 1);
+        self.load_translation_input();
+    }
+
+    /// Key/value pairs the form currently describes.
+    pub fn form_values(&self) -> Vec<(&'static str, Value)> {
+        let entry_type = self.entry_type();
+        FIELDS
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.is_key() && f.shown_for(entry_type))
+            .map(|(index, field)| {
+                let value = match field.widget {
+                    Widget::Categories => Value::List(self.categories.clone()),
+                    // "Application (other)" only relaxes validation.
+                    Widget::Dropdown if field.key == "Type" => {
+                        Value::String(entry_type.to_string())
+                    }
+                    _ => Value::parse(field.key, self.input[index].value()),
+                };
+                (field.key, value)
+            })
+            .collect()
+    }
+
+    /// The `Type=` value; "Application (other)" only relaxes validation.
+    pub fn entry_type(&self) -> &str {
+        match self.value("Type") {
+            "Application (other)" => "Application",
+            other => other,
+        }
+    }
+
+    /// The desktop file ID the launcher is saved as.
+    pub fn desktop_id(&self) -> String {
+        id::normalize(self.value(ID))
+    }
+
+    /// Keeps Name and ID in step after the focused field was typed in.
+    pub fn field_changed(&mut self) {
+        match schema::field(self.block_index).map(|f| f.key) {
+            Some("Name") => self.sync_id(),
+            Some(ID) => self.id_edited = true,
             _ => {}
         }
+    }
 
-        writeln!(file, "Icon={}", self.input[IDX_ICON])?;
-        writeln!(file, "Version={}", self.input[IDX_VERSION])?;
-        writeln!(file, "Comment={}", self.input[IDX_COMMENT])?;
-        writeln!(file, "Actions={}", self.input[IDX_ACTION])?;
-        writeln!(
-            file,
-            "NoDisplay={}",
-            if self.checkbox_nodisplay {
-                "true"
-            } else {
-                "false"
+    /// Fills the ID in from Name until the user types one.
+    pub fn sync_id(&mut self) {
+        if !self.id_edited {
+            self.input[schema::index(ID)] = Input::from(id::from_name(self.value("Name")));
+        }
+    }
+
+    /// File naming problems of `input` as the ID, plus an error when it
+    /// belongs to a different launcher. `DBusActivatable` launchers need a
+    /// valid D-Bus name, so for them warnings become errors.
+    pub fn id_diagnostics(&self, input: &str) -> Vec<Diagnostic> {
+        let id = id::normalize(input);
+        let mut diagnostics = id::check(&id);
+        if self.document.get_bool("DBusActivatable") == Some(true) {
+            for diagnostic in &mut diagnostics {
+                if diagnostic.severity == Severity::Warning {
+                    diagnostic.severity = Severity::Error;
+                }
             }
-        )?;
-        writeln!(
-            file,
-            "StartupNotify={}",
-            if self.checkbox_nodisplay {
-                "true"
-            } else {
-                "false"
+        }
+
+        let renamed = self.file_name.as_ref() != Some(&id);
+        if !lint::has_errors(&diagnostics) && renamed && id::user_path(&id).exists() {
+            diagnostics.insert(
+                0,
+                Diagnostic {
+                    severity: Severity::Error,
+                    line: None,
+                    message: format!("{id} already exists"),
+                    spec: lint::SPEC_NAMING,
+                },
+            );
+        }
+        diagnostics
+    }
+
+    /// Spec problems in `entry`, the launcher that saving would write, from
+    /// the same checks `--check` runs, and problems with its ID.
+    pub fn lint(&self, entry: &DesktopEntry) -> Vec<Diagnostic> {
+        let mut diagnostics = lint::check(entry);
+        diagnostics.extend(self.id_diagnostics(self.value(ID)));
+        diagnostics
+    }
+
+    /// Builds the launcher described by the form. When editing, only the
+    /// fields the user changed are written into the loaded document.
+    pub fn to_desktop_entry(&self) -> DesktopEntry {
+        let mut entry = self.document.clone();
+
+        for (key, value) in self.form_values() {
+            if self.edit && self.snapshot.iter().any(|(k, v)| *k == key && *v == value) {
+                continue;
             }
-        )?;
-        writeln!(
-            file,
-            "Terminal={}",
-            if self.checkbox_terminal {
-                "true"
-            } else {
-                "false"
+            if entry.get(key).is_none() && value.is_empty() {
+                continue;
             }
-        )?;
-        writeln!(file, "Type={}", self.input[IDX_TYPE])?;
-        writeln!(
-            file,
-            "Category={}",
-            if self.input[IDX_CATEGORY].value().eq("None") {
-                ""
-            } else {
-                self.input[IDX_CATEGORY].value()
+            entry.set(key, value);
+        }
+
+        if !self.edit || self.actions != self.actions_snapshot {
+            actions::write(&mut entry, &self.actions);
+        }
+
+        if self.translations != self.translations_snapshot {
+            locale::write(&mut entry, &schema::localized_keys(), &self.translations);
+        }
+
+        entry
+    }
+
+    /// Saves under the ID field. When the ID of an existing launcher was
+    /// changed, its file is renamed first, which never replaces another
+    /// launcher, and what still uses the old ID ends up in `warnings`.
+    /// If writing then fails, the file is moved back. Returns false when
+    /// nothing was saved, with the reason in `message` unless the ID field
+    /// already shows it.
+    pub fn save(&mut self) -> bool {
+        let id = self.desktop_id();
+        if lint::has_errors(&self.id_diagnostics(&id)) {
+            return false;
+        }
+
+        let content = self.to_desktop_entry().serialize();
+        let target = id::user_path(&id);
+        let result = match self.file_name.clone() {
+            Some(old) if old == id => atomic::write(&target, &content),
+            Some(old) if id::user_path(&old).is_file() => {
+                rename::move_to(&id::user_path(&old), &id)
+                    .and_then(|_| {
+                        atomic::write(&target, &content).inspect_err(|_| {
+                            let _ = rename::move_to(&target, &old);
+                        })
+                    })
+                    .inspect(|_| self.warnings = rename::stale_references(&old))
             }
-        )?;
-        Ok(())
-    }
-
-    // pub fn can_save(&self) -> bool {
-    //     let name = self.input[IDX_NAME].value().trim();
-    //     if name.is_empty() {
-    //         return false;
-    //     }
-
-    //     let file_name = format!("{}.desktop", name);
-
-    //     let save_path = dirs::data_dir()
-    //         .unwrap()
-    //         .join("applications")
-    //         .join(&file_name);
-
-    //     if !self.edit {
-    //         if save_path.exists() {
-    //             return false;
-    //         }
-    //     } else {
-    //         true;
-    //     }
-
-    //     match self.input[IDX_TYPE].value() {
-    //         "Link" => {
-    //             let url = self.input[IDX_URL].value().trim();
-
-    //             if url.is_empty() {
-    //                 return false;
-    //             }
-
-    //             if url.starts_with("file://") {
-    //                 if let Some(local_path) = url.strip_prefix("file://") {
-    //                     let path = Path::new(local_path);
-    //                     return path.exists();
-    //                 }
-    //             }
-
-    //             url.starts_with("https://")
-    //                 || url.starts_with("http://")
-    //                 || url.starts_with("mailto:")
-    //                 || url.starts_with("smb://")
-    //                 || url.starts_with("trash:///")
-    //                 || url.starts_with("recent:///")
-    //         }
-    //         "Application" => {
-    //             let trimmed = self.input[IDX_EXEC].value().trim();
-    //             let parts: Vec<&str> = trimmed.split_whitespace().collect();
-    //             let special_path = parts.iter().any(|p| p.contains("//"));
-
-    //             if special_path {
-    //                return true; 
-    //             }
-
-    //             if let Some(exec_raw) = parts.iter().find(|&&p| p.contains("/")) {
-    //                 let exec_path = Path::new(exec_raw);
-
-    //                 exec_path.exists()
-    //             } else {
-    //                 false
-    //             }
-    //         }
-    //         _ => true,
-    //     }
-    // }
-
-    pub fn validate_path(&self, input: &str, exts: &[&str], index: usize) -> (Style, String) {
-        let trimmed = input.trim();
-        let path = Path::new(trimmed);
+            // A new launcher, or a system one overridden under a new ID.
+            _ => atomic::create(&target, &content),
+        };
+
+        match result {
+            std::result::Result::Ok(()) => true,
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                self.message = Some(format!("{id} already exists"));
+                false
+            }
+            Err(e) => {
+                self.message = Some(format!("Can't save {id}: {e}"));
+                false
+            }
+        }
+    }
 
+    /// Status of the focused field at `index`: " - Empty" when its Type
+    /// needs a value, otherwise what its validator says.
+    pub fn status(&self, index: usize) -> Status {
+        let Some(field) = schema::field(index) else {
+            return (Style::default(), "".to_string());
+        };
         if self.block_index != index {
             return (Style::default(), "".to_string());
         }
 
-        if !path.exists() {
-            match self.input[IDX_TYPE].value() {
-                "Application (other)" | "Directory" => {
-                    return (
-                        self.is_active_block_style(IDX_EXEC),
-                        "- Ignored".to_string(),
-                    );
-                }
-                _ => {}
+        let value = self.input[index].value();
+        if value.trim().is_empty() {
+            if field.required_for.contains(&self.entry_type()) {
+                return (Style::default().fg(Color::LightRed), " - Empty".to_string());
             }
+            return (self.is_active_block_style(index), "".to_string());
         }
-
-        if trimmed.is_empty() {
-            match index {
-                IDX_EXEC => {
-                    return (Style::default().fg(Color::LightRed), " - Empty".to_string());
-                }
-                IDX_ICON => {
-                    return (Style::default().fg(Color::LightRed), " - Empty".to_string());
-                }
-                _ => {}
+        match field.validate {
+            Some(validate) => validate(self, value),
+            None if field.widget.is_boxed() => {
+                (Style::default().fg(Color::LightGreen), " - OK".to_string())
             }
+            None => (self.is_active_block_style(index), "".to_string()),
         }
+    }
 
-        if self.input[IDX_TYPE].value().eq("Link") {
-            if trimmed.is_empty() {
-                return (Style::default().fg(Color::LightRed), " - Empty".to_string());
-            }
+    pub fn validate_url(&self, input: &str) -> Status {
+        let trimmed = input.trim();
 
-            if !(trimmed.starts_with("file://")
-                || trimmed.starts_with("https://")
-                || trimmed.starts_with("http://")
-                || trimmed.starts_with("mailto:")
-                || trimmed.starts_with("smb://")
-                || trimmed.starts_with("trash:///")
-                || trimmed.starts_with("recent:///"))
-            {
+        if !(trimmed.starts_with("file://")
+            || trimmed.starts_with("https://")
+            || trimmed.starts_with("http://")
+            || trimmed.starts_with("mailto:")
+            || trimmed.starts_with("smb://")
+            || trimmed.starts_with("trash:///")
+            || trimmed.starts_with("recent:///"))
+        {
+            return (
+                Style::default().fg(Color::Yellow),
+                " - Invalid scheme".to_string(),
+            );
+        }
+
+        if trimmed.starts_with("file://")
+            && let Some(local_path) = trimmed.strip_prefix("file://")
+        {
+            let path = Path::new(local_path);
+            if !path.exists() {
                 return (
-                    Style::default().fg(Color::Yellow),
-                    " - Invalid scheme".to_string(),
+                    Style::default().fg(Color::LightRed),
+                    "- Not found".to_string(),
                 );
             }
-
-            if trimmed.starts_with("file://") {
-                if let Some(local_path) = trimmed.strip_prefix("file://") {
-                    let path = Path::new(local_path);
-                    if !path.exists() {
-                        return (
-                            Style::default().fg(Color::LightRed),
-                            "- Not found".to_string(),
-                        );
-                    }
-                }
-            }
-
-            return (Style::default().fg(Color::LightGreen), "- OK".to_string());
         }
 
-        let parts: Vec<&str> = trimmed.split_whitespace().collect();
-        let exec_path_opt = parts.iter().find(|&&p| p.contains('/'));
-
-        match exts.is_empty() {
-            true => {
-                if let Some(exec_path) = exec_path_opt {
-                    let path = Path::new(exec_path);
+        (Style::default().fg(Color::LightGreen), "- OK".to_string())
+    }
 
-                    if !path.exists() {
-                        return (
-                            Style::default().fg(Color::LightRed),
-                            " - Not found".to_string(),
-                        );
-                    }
+    /// Status of the Exec field, driven by the Exec tokenizer.
+    pub fn validate_exec(&self, input: &str) -> Status {
+        let args = match exec::parse(&unescape(input.trim())) {
+            std::result::Result::Ok(args) => args,
+            Err(e) => return (Style::default().fg(Color::LightRed), format!(" - {e}")),
+        };
 
-                    if !path.is_executable() {
-                        return (
-                            Style::default().fg(Color::Yellow),
-                            " - Unexpected type".to_string(),
-                        );
-                    }
+        let status = match exec::resolve(&args[0].text) {
+            Resolved::Found(path) => (
+                Style::default().fg(Color::LightGreen),
+                format!(" - OK ({})", path.display()),
+            ),
+            Resolved::NotExecutable(path) => {
+                return (
+                    Style::default().fg(Color::Yellow),
+                    format!(" - Not executable ({})", path.display()),
+                );
+            }
+            Resolved::NotFound => {
+                return match self.value("Type") {
+                    "Application (other)" => (
+                        self.is_active_block_style(self.block_index),
+                        " - Ignored".to_string(),
+                    ),
+                    _ => (
+                        Style::default().fg(Color::LightRed),
+                        " - Not found".to_string(),
+                    ),
+                };
+            }
+        };
 
-                    return (Style::default().fg(Color::LightGreen), " - OK".to_string());
+        let try_exec = unescape(self.value("TryExec").trim());
+        if !try_exec.is_empty() {
+            match exec::resolve(&try_exec) {
+                Resolved::Found(_) => {}
+                Resolved::NotExecutable(path) => {
+                    return (
+                        Style::default().fg(Color::Yellow),
+                        format!(" - TryExec not executable ({})", path.display()),
+                    );
+                }
+                Resolved::NotFound => {
+                    return (
+                        Style::default().fg(Color::Yellow),
+                        format!(" - TryExec {try_exec} not found"),
+                    );
                 }
             }
-            false => {}
         }
 
-        if !exts.is_empty() {
-            if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                if exts.iter().any(|&v| v.eq_ignore_ascii_case(ext)) {
-                    return (Style::default().fg(Color::LightGreen), " - OK".to_string());
+        status
+    }
+
+    /// Status of the Icon field: the file the icon name resolves to in the
+    /// current icon theme, or whether it is missing.
+    pub fn validate_icon(&self, input: &str) -> Status {
+        match self.icons.lookup(input.trim(), icons::DEFAULT_SIZE) {
+            Some(path) => {
+                let supported = path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|ext| icons::EXTENSIONS.contains(&ext));
+                if supported {
+                    (
+                        Style::default().fg(Color::LightGreen),
+                        format!(" - OK ({})", path.display()),
+                    )
+                } else {
+                    (
+                        Style::default().fg(Color::Yellow),
+                        " - Unexpected type".to_string(),
+                    )
                 }
             }
-            return (
-                Style::default().fg(Color::Yellow),
-                " - Unexpected type".to_string(),
-            );
+            None => (
+                Style::default().fg(Color::LightRed),
+                " - Not found".to_string(),
+            ),
         }
-
-        return (Style::default().fg(Color::LightGreen), " - OK".to_string());
     }
 
-    pub fn validate_name(&self, input: &str, index: usize) -> (Style, String) {
-        let trimmed = input.trim();
-
-        if self.block_index != index {
-            return (Style::default(), "".to_string());
+    pub fn validate_version(&self, input: &str) -> Status {
+        if lint::VERSIONS.contains(&input.trim()) {
+            return (self.is_active_block_style(self.block_index), "".to_string());
         }
+        (
+            Style::default().fg(Color::Yellow),
+            " - Unknown spec version".to_string(),
+        )
+    }
 
-        if self.edit {
+    pub fn validate_keywords(&self, input: &str) -> Status {
+        if split_list(input.trim()).iter().any(|k| k.trim().is_empty()) {
             return (
-                Style::default().fg(Color::LightGreen),
-                " - Ignored".to_string(),
+                Style::default().fg(Color::Yellow),
+                " - Empty keyword".to_string(),
             );
         }
+        (self.is_active_block_style(self.block_index), "".to_string())
+    }
 
-        if trimmed.is_empty() {
-            return (Style::default().fg(Color::LightRed), " - Empty".to_string());
+    /// Status of TryExec: menus hide the launcher when it isn't found.
+    pub fn validate_try_exec(&self, input: &str) -> Status {
+        match exec::resolve(&unescape(input.trim())) {
+            Resolved::Found(path) => (
+                Style::default().fg(Color::LightGreen),
+                format!(" - OK ({})", path.display()),
+            ),
+            Resolved::NotExecutable(path) => (
+                Style::default().fg(Color::Yellow),
+                format!(" - Not executable ({})", path.display()),
+            ),
+            Resolved::NotFound => (
+                Style::default().fg(Color::Yellow),
+                " - Not found, menus will hide it".to_string(),
+            ),
         }
+    }
 
-        let file_name = format!("{}.desktop", trimmed);
-
-        let save_path = dirs::data_local_dir()
-            .unwrap()
-            .join("applications")
-            .join(&file_name);
-
-        if save_path.exists() {
-            return (
+    /// Status of Path, the directory the program runs in.
+    pub fn validate_working_dir(&self, input: &str) -> Status {
+        let path = PathBuf::from(unescape(input.trim()));
+        if !path.is_absolute() {
+            (
+                Style::default().fg(Color::Yellow),
+                " - Not an absolute path".to_string(),
+            )
+        } else if path.is_dir() {
+            (Style::default().fg(Color::LightGreen), " - OK".to_string())
+        } else if path.exists() {
+            (
                 Style::default().fg(Color::LightRed),
-                " - Already exists".to_string(),
-            );
+                " - Not a directory".to_string(),
+            )
+        } else {
+            (
+                Style::default().fg(Color::Yellow),
+                " - Not found".to_string(),
+            )
         }
+    }
+
+    pub fn validate_mime_types(&self, input: &str) -> Status {
+        match split_list(input.trim())
+            .iter()
+            .find(|m| !lint::is_mime_type(m))
+        {
+            Some(mime) => (
+                Style::default().fg(Color::Yellow),
+                format!(" - \"{mime}\" is not a MIME type"),
+            ),
+            None => (self.is_active_block_style(self.block_index), "".to_string()),
+        }
+    }
 
+    /// Status of the ID field: its file naming problems, or that another
+    /// launcher already has it.
+    pub fn validate_name(&self, input: &str) -> Status {
+        let diagnostics = self.id_diagnostics(input);
+        for severity in [Severity::Error, Severity::Warning] {
+            if let Some(first) = diagnostics.iter().find(|d| d.severity == severity) {
+                let color = match severity {
+                    Severity::Error => Color::LightRed,
+                    _ => Color::Yellow,
+                };
+                return (Style::default().fg(color), format!(" - {}", first.message));
+            }
+        }
         (Style::default().fg(Color::LightGreen), " - OK".to_string())
     }
+
     pub fn is_active_block_style(&self, index: usize) -> Style {
-        if self.block_index == index && index == IDX_CANCEL {
+        if self.block_index == index && index == CANCEL {
             Style::default().fg(Color::LightRed)
         } else if self.block_index == index {
             Style::default().fg(Color::LightGreen)
@@ -431,12 +881,15 @@ impl App {
         }
     }
 
+    /// Whether the checkbox at `index` is ticked.
+    pub fn checked(&self, index: usize) -> bool {
+        self.input[index].value() == "true"
+    }
+
     pub fn checkbox(&mut self) {
-        match self.block_index {
-            IDX_NODISPLAY => self.checkbox_nodisplay = !self.checkbox_nodisplay,
-            IDX_TERMINAL => self.checkbox_terminal = !self.checkbox_terminal,
-            IDX_STARTUPNOTIFY => self.checkbox_startupnotify = !self.checkbox_startupnotify,
-            _ => {}
+        if schema::field(self.block_index).is_some_and(|f| f.widget == Widget::Checkbox) {
+            let checked = !self.checked(self.block_index);
+            self.input[self.block_index] = Input::from(checked.to_string());
         }
     }
 
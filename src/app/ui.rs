@@ -1,15 +1,25 @@
+use crate::app::schema::{self, CANCEL, FIELDS, SAVE, Widget};
 use crate::app::{state::App, state::InputMode};
 use crate::utils::constants::*;
 
 use color_eyre::eyre::{Ok, Result};
+use deskforge::desktop::entry::split_locale;
+use deskforge::desktop::lint::{Diagnostic, Severity};
+use deskforge::desktop::value::escape;
+use deskforge::desktop::{actions, categories, locale};
+use deskforge::diff::{self, Mark};
 
-use ratatui::widgets::Wrap;
+use ratatui::widgets::{Widget as _, Wrap};
 use ratatui::{
     Frame,
+    buffer::Buffer,
     layout::{Constraint, Layout, Position, Rect},
     style::{Color, Modifier, Style, Stylize},
-    text::Line,
-    widgets::{Block, List, ListItem, Paragraph},
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState,
+    },
 };
 
 impl App {
@@ -22,8 +32,7 @@ impl App {
         Ok(())
     }
 
-    pub fn draw(&self, frame: &mut Frame) {
-        let area;
+    pub fn draw(&mut self, frame: &mut Frame) {
         let frame_width = frame.area().width;
         let frame_height = frame.area().height;
 
@@ -56,16 +65,30 @@ impl App {
             return;
         }
 
-        let horizontal_margin = if frame_width > HALF_SCREEN { 20 } else { 0 };
+        // Wide terminals show the file that will be written next to the form.
+        let [form_column, preview_column] = if frame_width > HALF_SCREEN {
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(frame.area())
+        } else {
+            [frame.area(), Rect::default()]
+        };
 
         let vertical = Layout::vertical([
             Constraint::Length(1),
             Constraint::Min(0),
             Constraint::Length(3),
-        ])
-        .horizontal_margin(horizontal_margin);
+        ]);
+
+        let [_instructions_area, outline_area, status_area] = vertical.areas(form_column);
 
-        let [_instructions_area, outline_area, _desktop_name] = vertical.areas(frame.area());
+        if let Some(message) = &self.message {
+            frame.render_widget(
+                Paragraph::new(format!(" {message}"))
+                    .style(Style::default().fg(Color::LightRed))
+                    .add_modifier(Modifier::BOLD),
+                status_area,
+            );
+        }
 
         let (msg, _style) = match self.input_mode {
             InputMode::Normal => (
@@ -79,287 +102,190 @@ impl App {
         };
 
         let vim_mode = match self.input_mode {
-            InputMode::Normal => {
-                Line::from(Line::from(vec![" Insert: ".into(), "<I> ".white().bold()]))
-            }
+            InputMode::Normal => Line::from(vec![" Insert: ".into(), "<I> ".white().bold()]),
 
-            InputMode::Insert => Line::from(Line::from(vec![
-                " Normal: ".into(),
-                "<Esc> ".white().bold(),
-            ])),
+            InputMode::Insert => Line::from(vec![" Normal: ".into(), "<Esc> ".white().bold()]),
         }
         .centered();
 
-        let instructions = Line::from(
-            Line::from(vec![
-                " Next ".into(),
-                "<J> ".white().bold(),
-                "─".into(),
-                " Previous ".into(),
-                "<K> ".white().bold(),
-                "─".into(),
-                " Quit ".into(),
-                "<Q> ".white().bold(),
-            ])
-            .centered(),
-        );
+        let mut instructions = vec![
+            " Next ".into(),
+            "<J> ".white().bold(),
+            "─".into(),
+            " Previous ".into(),
+            "<K> ".white().bold(),
+            "─".into(),
+            " Quit ".into(),
+            "<Q> ".white().bold(),
+        ];
+        if self.input_mode == InputMode::Normal && self.localized_key().is_some() {
+            instructions.extend(["─".into(), " Translate ".into(), "<T> ".white().bold()]);
+        }
+        let instructions = Line::from(instructions).centered();
 
         let outline_block = Block::bordered()
             .title_bottom(msg)
             .title_bottom(vim_mode)
             .title_bottom(instructions)
-            .title(Line::from(
-                " DeskForge - Create Launcher ".bold().into_centered_line(),
-            ));
+            .title(" DeskForge - Create Launcher ".bold().into_centered_line());
 
         frame.render_widget(&outline_block, outline_area);
 
         let inner = outline_block.inner(outline_area);
-        let [required_area, optional_area, button_area] = *Layout::vertical([
-            Constraint::Length(11),
+        let [form_area, button_area] = Layout::vertical([
             Constraint::Min(0),
-            Constraint::Length(3),
+            Constraint::Length(if inner.height >= 16 { 3 } else { 1 }),
         ])
-        .split(inner) else {
-            unreachable!()
+        .areas(inner);
+
+        // The form is laid out from the schema: text boxes in the Required
+        // section, one row per field in the Optional one. It is drawn at its
+        // full height and scrolled when the terminal is shorter than that.
+        let shown: Vec<usize> = (0..FIELDS.len()).filter(|&i| self.is_shown(i)).collect();
+        let (boxed, rows): (Vec<usize>, Vec<usize>) =
+            shown.iter().partition(|&&i| FIELDS[i].widget.is_boxed());
+        let box_heights: Vec<u16> = boxed
+            .iter()
+            .map(|&i| self.box_height(i, form_area.width.saturating_sub(5).max(1)))
+            .collect();
+        let required_height = box_heights.iter().sum::<u16>() + 2;
+        let optional_height = rows.len() as u16 * 2 + 2;
+        let overflows = required_height + optional_height > form_area.height;
+
+        // One column is left for the scrollbar.
+        let content = Rect {
+            width: form_area.width - u16::from(overflows),
+            height: (required_height + optional_height).max(form_area.height),
+            ..Rect::default()
         };
+        let text_width = content.width.saturating_sub(4).max(1);
+        let mut buf = Buffer::empty(content);
+
+        let [required_area, optional_area] =
+            Layout::vertical([Constraint::Length(required_height), Constraint::Min(0)])
+                .areas(content);
 
         // Require & Optional area
-        let requireed_block = Block::bordered().title("Required");
+        let required_block = Block::bordered().title("Required");
         let optional_block = Block::bordered().title("Optional");
-
-        // Require & Block inner
-        let required_inner = requireed_block.inner(required_area);
-        let [name_area, exec_area, icon_area] = *Layout::vertical([
-            Constraint::Length(3), // Name
-            Constraint::Min(3),    // Exec
-            Constraint::Length(3), // Icon
-        ])
-        .split(required_inner) else {
-            unreachable!()
-        };
-
+        let required_inner = required_block.inner(required_area);
         let optional_inner = optional_block.inner(optional_area);
-        let [
-            version_area,
-            comment_area,
-            action_area,
-            nodisplay_area,
-            startupnotify_area,
-            terminal_area,
-            type_area,
-            category_area,
-        ] = *Layout::vertical([
-            Constraint::Length(2), // Command
-            Constraint::Length(2), // Comment
-            Constraint::Length(2), // Action
-            Constraint::Length(2), // NoDisplay
-            Constraint::Length(2), // StartUpNotify
-            Constraint::Length(2), // Terminal
-            Constraint::Length(2), // Type
-            Constraint::Length(2), // Category
-        ])
-        .split(optional_inner)
-        else {
-            unreachable!()
-        };
-
-        frame.render_widget(requireed_block, required_area);
-        frame.render_widget(optional_block, optional_area);
-
-        // Button layout
-        let buttons_area = Layout::horizontal([
-            Constraint::Percentage(40),
-            Constraint::Length(20),
-            Constraint::Length(20),
-            Constraint::Percentage(40),
-        ])
-        .split(button_area);
-
-        // Desktop name block
-        let (name_style, name_status) = self.validate_name(self.input[IDX_NAME].value(), IDX_NAME);
-        let name = Paragraph::new(self.input[IDX_NAME].value())
-            .style(name_style)
-            .block(Block::bordered().title(format!("Name{}", name_status)))
-            .add_modifier(Modifier::BOLD);
-        frame.render_widget(name, name_area);
-
-        // Exec & URL block
-        let type_value = self.input[IDX_TYPE].value();
-        let mut exec_or_url_area = Rect {
-            x: exec_area.x,
-            y: 6,
-            width: exec_area.width.max(1),
-            height: 3,
-        };
-
-        match type_value {
-            "Link" => {
-                let (url_style, url_status) =
-                    self.validate_path(self.input[IDX_URL].value(), &[], IDX_URL);
-                let url = Paragraph::new(self.input[IDX_URL].value())
-                    .style(url_style)
-                    .block(Block::bordered().title(format!("URL{}", url_status)))
-                    .add_modifier(Modifier::BOLD);
-                frame.render_widget(url, exec_or_url_area);
+        required_block.render(required_area, &mut buf);
+        optional_block.render(optional_area, &mut buf);
+
+        let mut areas: Vec<Option<Rect>> = vec![None; FIELDS.len()];
+
+        let box_areas = Layout::vertical(box_heights.iter().map(|&h| Constraint::Length(h)))
+            .split(required_inner);
+        for (&index, &area) in boxed.iter().zip(box_areas.iter()) {
+            areas[index] = Some(area);
+            let field = &FIELDS[index];
+            let (style, status) = self.status(index);
+            let mut text = Paragraph::new(self.input[index].value())
+                .style(style)
+                .block(
+                    Block::bordered()
+                        .title(format!(
+                            "{}{}{status}",
+                            field.label,
+                            self.translated_label(field.key)
+                        ))
+                        .border_style(style),
+                )
+                .add_modifier(Modifier::BOLD);
+            if field.widget == Widget::Multiline {
+                text = text.wrap(Wrap { trim: true });
             }
+            text.render(area, &mut buf);
+        }
 
-            _ => {
-                let input_len = self.input[IDX_EXEC].value().len();
-
-                match self.block_index {
-                    IDX_EXEC => {
-                        exec_or_url_area.height =
-                            ((input_len / (exec_or_url_area.width - 1) as usize) + 3) as u16;
-                        if input_len >= (exec_or_url_area.width - 2) as usize {
-                            if exec_or_url_area.height >= 6 {
-                                exec_or_url_area.height = 6;
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-
-                let (exec_color, exec_status) =
-                    self.validate_path(self.input[IDX_EXEC].value(), &[], IDX_EXEC);
-                let exec = Paragraph::new(self.input[IDX_EXEC].value())
-                    .style(exec_color)
-                    .block(
-                        Block::bordered()
-                            .title(format!("Exec{}", exec_status))
-                            .border_style(exec_color),
-                    )
-                    .wrap(Wrap { trim: true })
-                    .add_modifier(Modifier::BOLD);
-
-                frame.render_widget(exec, exec_or_url_area);
-            }
+        let row_areas =
+            Layout::vertical(vec![Constraint::Length(2); rows.len()]).split(optional_inner);
+        for (&index, &area) in rows.iter().zip(row_areas.iter()) {
+            areas[index] = Some(area);
+            self.draw_row(&mut buf, index, area);
         }
 
-        // Icon block
-        if ((self.input[IDX_EXEC].value().len() / (exec_or_url_area.width - 1) as usize) + 3) < 4
-            || self.block_index != IDX_EXEC
+        let mut focus = areas.get(self.block_index).copied().flatten();
+        if self.dropdown_open
+            && let Some(area) = focus.as_mut()
         {
-            let (icon_style, icon_status) = self.validate_path(
-                self.input[IDX_ICON].value(),
-                &["png", "svg", "jpg"],
-                IDX_ICON,
+            area.height += self.dropdown_options.len() as u16;
+        }
+        self.follow_focus(focus, form_area.height, content.height);
+
+        let scroll = self.scroll;
+        for y in 0..form_area.height {
+            for x in 0..content.width {
+                frame.buffer_mut()[(form_area.x + x, form_area.y + y)] =
+                    buf[(x, y + scroll)].clone();
+            }
+        }
+        if overflows {
+            let mut state = ScrollbarState::new((content.height - form_area.height) as usize + 1)
+                .position(scroll as usize)
+                .viewport_content_length(form_area.height as usize);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight),
+                form_area,
+                &mut state,
             );
-            let icon = Paragraph::new(self.input[IDX_ICON].value())
-                .style(icon_style)
-                .block(
-                    Block::bordered()
-                        .title(format!("Icon{}", icon_status))
-                        .border_style(icon_style),
-                )
-                .add_modifier(Modifier::BOLD);
-            frame.render_widget(icon, icon_area);
         }
 
-        // Command block
-        let version_style = self.is_active_block_style(IDX_VERSION);
-        let version = Paragraph::new(format!("Version: [ {}  ]", self.input[IDX_VERSION].value()))
-            .style(version_style)
-            .add_modifier(Modifier::BOLD);
-        frame.render_widget(version, version_area);
-
-        // Comment block
-        let comment_style = self.is_active_block_style(IDX_COMMENT);
-        let comment = Paragraph::new(format!("Comment: [ {}  ]", self.input[IDX_COMMENT].value()))
-            .style(comment_style)
-            .add_modifier(Modifier::BOLD);
-        frame.render_widget(comment, comment_area);
-
-        // Comment block
-        let action_style = self.is_active_block_style(IDX_ACTION);
-        let action = Paragraph::new(format!("Actions: [ {}  ]", self.input[IDX_ACTION].value()))
-            .style(action_style)
-            .add_modifier(Modifier::BOLD);
-        frame.render_widget(action, action_area);
-
-        // NoDisplay block
-        let nodisplay_style = self.is_active_block_style(IDX_NODISPLAY);
-        let nodisplay_label = if self.checkbox_nodisplay {
-            "NoDisplay: [ X ]"
-        } else {
-            "NoDisplay: [   ]"
+        // The part of the form an area shows on screen.
+        let view = Rect {
+            y: scroll,
+            height: form_area.height,
+            ..content
         };
-        let nodisplay = Paragraph::new(nodisplay_label)
-            .style(nodisplay_style)
-            .add_modifier(Modifier::BOLD);
-        frame.render_widget(nodisplay, nodisplay_area);
-
-        // NoDisplay block
-        let startupnotify_style = self.is_active_block_style(IDX_STARTUPNOTIFY);
-        let startupnotify_label = if self.checkbox_startupnotify {
-            "StartUpNotify: [ X ]"
-        } else {
-            "StartUpNotify: [   ]"
-        };
-        let startupnotify = Paragraph::new(startupnotify_label)
-            .style(startupnotify_style)
-            .add_modifier(Modifier::BOLD);
-        frame.render_widget(startupnotify, startupnotify_area);
-
-        // Terminal block
-        let terminal_style = self.is_active_block_style(IDX_TERMINAL);
-        let terminal_label = if self.checkbox_terminal {
-            "Terminal: [ X ]"
-        } else {
-            "Terminal: [   ]"
+        let on_screen = |area: Rect| {
+            let visible = area.intersection(view);
+            Rect {
+                x: form_area.x + visible.x,
+                y: form_area.y + visible.y - scroll,
+                ..visible
+            }
         };
-        let terminal = Paragraph::new(terminal_label)
-            .style(terminal_style)
-            .add_modifier(Modifier::BOLD);
-        frame.render_widget(terminal, terminal_area);
-
-        // Type
-        let arrow = if self.dropdown_open && self.dropdown_index == Some(IDX_TYPE) {
-            "▲"
+
+        // Panels cover the Optional section, or the whole form when too
+        // little of it is shown.
+        let visible_optional = on_screen(optional_area);
+        let panel_area = if visible_optional.height >= 8 {
+            visible_optional
         } else {
-            "▼"
+            form_area
         };
-        let type_label = format!("Type: [ {} {} ]", self.input[IDX_TYPE].value(), arrow);
-        frame.render_widget(
-            Paragraph::new(type_label).style(self.is_active_block_style(IDX_TYPE)),
-            type_area,
-        );
 
-        // Category
-        if !(self.dropdown_open && self.dropdown_index != Some(IDX_CATEGORY)) {
-            let arrow = if self.dropdown_open && self.dropdown_index == Some(IDX_CATEGORY) {
-                "▲"
-            } else {
-                "▼"
-            };
-            let category_label = format!(
-                "Category: [ {} {} ]",
-                self.input[IDX_CATEGORY].value(),
-                arrow
-            );
-            frame.render_widget(
-                Paragraph::new(category_label).style(self.is_active_block_style(IDX_CATEGORY)),
-                category_area,
-            );
+        if self.picker_open {
+            self.draw_picker(frame, panel_area);
         }
 
-        if self.dropdown_open {
-            let idx = self.dropdown_index.unwrap();
-            let area = match idx {
-                IDX_TYPE => type_area,
-                IDX_CATEGORY => category_area,
-                _ => return,
-            };
+        if self.actions_open {
+            self.draw_actions(frame, panel_area);
+        }
+
+        if self.translations_open {
+            self.draw_translations(frame, panel_area);
+        }
+
+        if self.dropdown_open
+            && let Some(idx) = self.dropdown_index
+            && let Some(area) = areas[idx]
+        {
+            // Options line up with the value after "Label: [ " and may hang
+            // over the end of the form.
             let dropdown_area = Rect {
-                x: if self.dropdown_index == Some(IDX_TYPE) {
-                    area.x + 8
-                } else {
-                    area.x + 12
-                },
-                y: area.y + area.height,
-                width: area.width,
+                x: form_area.x + area.x + FIELDS[idx].label.len() as u16 + 4,
+                y: (form_area.y + area.y + 1).saturating_sub(scroll),
+                width: self
+                    .dropdown_options
+                    .iter()
+                    .map(|o| o.len())
+                    .max()
+                    .unwrap_or(0) as u16,
                 height: self.dropdown_options.len() as u16,
-            };
+            }
+            .intersection(inner);
             let items: Vec<ListItem> = self
                 .dropdown_options
                 .iter()
@@ -374,30 +300,53 @@ impl App {
                 })
                 .collect();
 
+            frame.render_widget(Clear, dropdown_area);
             frame.render_widget(List::new(items).block(Block::default()), dropdown_area);
         }
 
+        // Button layout
+        let buttons_area = Layout::horizontal([
+            Constraint::Percentage(40),
+            Constraint::Length(20),
+            Constraint::Length(20),
+            Constraint::Percentage(40),
+        ])
+        .split(button_area);
+
+        // Built once per frame for both the preview and the save button.
+        let entry = self.to_desktop_entry();
+        let diagnostics = self.lint(&entry);
+        let text = entry.serialize();
+        if !preview_column.is_empty() {
+            let preview_area = Rect {
+                y: outline_area.y,
+                height: outline_area.height,
+                ..preview_column
+            };
+            self.draw_preview(frame, preview_area, &text, &diagnostics);
+        }
+
         // Buttons
-        // let save_style = if self.can_save() {
-        //     self.is_active_block_style(IDX_SAVE)
-        // } else if !self.can_save() && self.block_index == IDX_SAVE {
-        //     Style::default().fg(Color::LightRed)
-        // } else {
-        //     Style::default()
-        // };
-        // let save_label = if self.can_save() {
-        //     "[ SAVE ]"
-        // } else {
-        //     "[ CAN'T SAVE ]"
-        // };
-        let save_style = self.is_active_block_style(IDX_SAVE);
-        let save_label = "[ SAVE ]";
-        let save_btn = Paragraph::new(format!("{}", save_label))
+        let errors = diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .count();
+        let save_style = if errors > 0 && self.block_index == SAVE {
+            Style::default().fg(Color::LightRed)
+        } else {
+            self.is_active_block_style(SAVE)
+        };
+        let save_label = match errors {
+            0 => "[ SAVE ]".to_string(),
+            1 => "[ SAVE - 1 error ]".to_string(),
+            n => format!("[ SAVE - {n} errors ]"),
+        };
+        let save_btn = Paragraph::new(save_label)
             .style(save_style)
             .add_modifier(Modifier::BOLD)
             .alignment(ratatui::layout::Alignment::Center);
 
-        let cancel_style = self.is_active_block_style(IDX_CANCEL);
+        let cancel_style = self.is_active_block_style(CANCEL);
         let cancel_btn = Paragraph::new("[ CANCEL ]")
             .style(cancel_style)
             .add_modifier(Modifier::BOLD)
@@ -407,55 +356,526 @@ impl App {
         frame.render_widget(cancel_btn, buttons_area[2]);
 
         // Insert mode
-        match self.block_index {
-            IDX_NAME => area = name_area,
-            IDX_EXEC => area = exec_area,
-            IDX_ICON => area = icon_area,
-
-            IDX_VERSION => area = version_area,
-            IDX_COMMENT => area = comment_area,
-            IDX_ACTION => area = action_area,
-            IDX_NODISPLAY => return,
-            IDX_STARTUPNOTIFY => return,
-            IDX_TERMINAL => return,
-            IDX_TYPE => return,
-            IDX_CATEGORY => return,
-
-            IDX_SAVE => return,
-            IDX_CANCEL => return,
-            _ => area = name_area,
+        if self.input_mode == InputMode::Normal
+            || self.picker_open
+            || self.actions_open
+            || self.translations_open
+        {
+            return;
         }
+        let Some(field) = schema::field(self.block_index).filter(|f| f.widget.is_text()) else {
+            return;
+        };
+        let Some(area) = areas[self.block_index].map(on_screen) else {
+            return;
+        };
 
-        match self.input_mode {
-            InputMode::Normal => {}
-            InputMode::Insert => {
-                let (area_x, area_y): (u16, u16) = if self.block_index >= IDX_VERSION {
-                    (10, 0)
+        let cursor = self.input[self.block_index].visual_cursor() as u16;
+        let (cursor_x, cursor_y) = match field.widget {
+            // Typed after the "Label: [ " of the row.
+            Widget::Row => (area.x + field.label.len() as u16 + 4 + cursor, area.y),
+            Widget::Multiline => (
+                area.x + 1 + cursor % text_width,
+                area.y + 1 + cursor / text_width,
+            ),
+            _ => (area.x + 1 + cursor, area.y + 1),
+        };
+        let cursor = Position::new(
+            cursor_x.min(area.right().saturating_sub(2)),
+            cursor_y.min(area.bottom().saturating_sub(2)),
+        );
+        if form_area.contains(cursor) {
+            frame.set_cursor_position(cursor);
+        }
+    }
+
+    /// Scrolls the form just enough to show `focus` and the lines around
+    /// it. The top of a field taller than the form wins over its bottom.
+    fn follow_focus(&mut self, focus: Option<Rect>, view_height: u16, content_height: u16) {
+        if let Some(focus) = focus {
+            let top = focus.y.saturating_sub(1);
+            let bottom = focus.bottom() + 1;
+            if bottom > self.scroll + view_height {
+                self.scroll = bottom - view_height;
+            }
+            if top < self.scroll {
+                self.scroll = top;
+            }
+        }
+        self.scroll = self.scroll.min(content_height - view_height);
+    }
+
+    /// Height of a boxed field, growing to show a long focused value. Its
+    /// length is counted in terminal columns.
+    fn box_height(&self, index: usize, text_width: u16) -> u16 {
+        if FIELDS[index].widget != Widget::Multiline || self.block_index != index {
+            return 3;
+        }
+        let width = Span::raw(self.input[index].value()).width();
+        let lines = width / text_width as usize + 1;
+        (lines as u16 + 2).min(6)
+    }
+
+    /// The file saving would write, marked against the file on disk when
+    /// editing. Lines with errors are highlighted and the preview follows
+    /// the line of the focused field.
+    fn draw_preview(&self, frame: &mut Frame, area: Rect, text: &str, diagnostics: &[Diagnostic]) {
+        let lines: Vec<&str> = text.lines().collect();
+        let marks = self.disk.as_deref().map(|disk| diff::marks(disk, text));
+        let errors: Vec<&Diagnostic> = diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .collect();
+        let number_width = lines.len().to_string().len();
+
+        let content: Vec<Line> = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let (marker, color) = match marks.as_ref().and_then(|m| m.get(i)) {
+                    Some(Mark::Added) => ("+", Color::Green),
+                    Some(Mark::Changed) => ("~", Color::Yellow),
+                    Some(Mark::Removed) => ("-", Color::Red),
+                    _ => (" ", Color::Reset),
+                };
+                let error = errors.iter().any(|d| d.line == Some(i + 1));
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{:>number_width$} ", i + 1),
+                        Style::default().fg(if error { Color::Red } else { Color::DarkGray }),
+                    ),
+                    Span::styled(format!("{marker} "), Style::default().fg(color).bold()),
+                ];
+                if error {
+                    spans.push(Span::styled(
+                        *line,
+                        Style::default().fg(Color::White).bg(Color::Red),
+                    ));
+                } else {
+                    spans.extend(highlight(line));
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        let title = if self.value(schema::ID).trim().is_empty() {
+            " Preview ".to_string()
+        } else {
+            format!(" Preview: {} ", self.desktop_id())
+        };
+        let mut block = Block::bordered().title(title);
+        if let Some(first) = errors.first() {
+            block = block.title_bottom(Line::from(format!(" ⚠ {first} ")).red());
+        }
+        let inner = block.inner(area);
+
+        // Keep the line of the focused field in the middle when it can be.
+        let focused = schema::field(self.block_index).and_then(|field| {
+            lines.iter().position(|line| {
+                line.split_once('=')
+                    .is_some_and(|(key, _)| key.trim_end() == field.key)
+            })
+        });
+        let scroll = focused
+            .unwrap_or(0)
+            .saturating_sub(inner.height as usize / 2)
+            .min(lines.len().saturating_sub(inner.height as usize));
+
+        frame.render_widget(
+            Paragraph::new(content)
+                .block(block)
+                .scroll((scroll as u16, 0)),
+            area,
+        );
+    }
+
+    /// One row of the Optional section.
+    fn draw_row(&self, buf: &mut Buffer, index: usize, area: Rect) {
+        let field = &FIELDS[index];
+        let style = self.is_active_block_style(index);
+        let label = field.label;
+
+        let row = match field.widget {
+            Widget::Checkbox => {
+                let mark = if self.checked(index) { "X" } else { " " };
+                Paragraph::new(format!("{label}: [ {mark} ]")).style(style)
+            }
+            Widget::Dropdown => {
+                let arrow = if self.dropdown_index == Some(index) {
+                    "▲"
+                } else {
+                    "▼"
+                };
+                let value = self.input[index].value();
+                Paragraph::new(format!("{label}: [ {value} {arrow} ]")).style(style)
+            }
+            Widget::Actions => {
+                let action_ids: Vec<&str> = self
+                    .actions
+                    .iter()
+                    .filter(|a| !a.id.is_empty())
+                    .map(|a| a.id.as_str())
+                    .collect();
+                let action_label = if action_ids.is_empty() {
+                    "None".to_string()
                 } else {
-                    (0, 1)
+                    action_ids.join(", ")
                 };
-                
-                let input_len = self.input[IDX_EXEC].value().len();
-                let current_cursor = self.input[self.block_index].visual_cursor() as u16;
-                let mut cursor_x = area.x + area_x + current_cursor + 1;
-                let mut cursor_y = area.y.saturating_add(area_y);
-
-                if self.block_index == IDX_EXEC {
-                    let width = exec_or_url_area.width.saturating_sub(1);
-                    let scale = ((input_len / (exec_or_url_area.width - 1) as usize)) as u16;
-                    let line_index = current_cursor / width;
-                    let column_index = current_cursor % width;
-
-                    cursor_x = area.x + area_x + column_index + scale + 1;
-                    cursor_y = area.y + area_y + line_index;
-
-                    cursor_x = cursor_x.min(area.x + exec_or_url_area.width - 1);
-                    cursor_y = cursor_y.min(area.y + exec_or_url_area.height - 1);
+                Paragraph::new(format!("{label}: [ {} ▶ ]", action_label)).style(style)
+            }
+            Widget::Categories => {
+                let mut chips = vec![Span::styled(format!("{label}: "), style)];
+                if self.categories.is_empty() {
+                    chips.push(Span::styled("[ None ]", style));
+                }
+                for category in &self.categories {
+                    let color = if categories::is_main(category) {
+                        Color::Blue
+                    } else if categories::is_known(category) {
+                        Color::DarkGray
+                    } else {
+                        Color::Red
+                    };
+                    chips.push(Span::styled(
+                        format!(" {category} "),
+                        Style::default().bg(color).fg(Color::White),
+                    ));
+                    chips.push(" ".into());
                 }
 
-                #[allow(clippy::cast_possible_truncation)]
-                frame.set_cursor_position(Position::new(cursor_x, cursor_y))
+                let warnings = categories::check(&self.categories);
+                let warning = match warnings.first() {
+                    Some(first) if warnings.len() > 1 => {
+                        format!("⚠ {first} (+{} more)", warnings.len() - 1)
+                    }
+                    Some(first) => format!("⚠ {first}"),
+                    None => String::new(),
+                };
+
+                Paragraph::new(vec![
+                    Line::from(chips),
+                    Line::from(warning).style(Style::default().fg(Color::Yellow)),
+                ])
+            }
+            _ => {
+                let (style, status) = self.status(index);
+                Paragraph::new(format!(
+                    "{label}: [ {}  ]{}{status}",
+                    self.input[index].value(),
+                    self.translated_label(field.key)
+                ))
+                .style(style)
             }
+        };
+        row.add_modifier(Modifier::BOLD).render(area, buf);
+    }
+    fn draw_picker(&self, frame: &mut Frame, area: Rect) {
+        let options = self.picker_options();
+        let warnings = categories::check(&self.categories);
+
+        let block = Block::bordered()
+            .title(" Categories ")
+            .title_bottom(Line::from(vec![
+                " Toggle ".into(),
+                "<Space> ".white().bold(),
+                "─".into(),
+                " Done ".into(),
+                "<Enter> ".white().bold(),
+            ]))
+            .border_style(Style::default().fg(Color::LightGreen));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let [search_area, list_area, warning_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(warnings.len().min(3) as u16),
+        ])
+        .areas(inner);
+
+        frame.render_widget(
+            Paragraph::new(format!("Search: {}", self.picker_query.value())),
+            search_area,
+        );
+
+        let items: Vec<ListItem> = options
+            .iter()
+            .map(|option| {
+                let checked = if self.categories.iter().any(|c| c == option) {
+                    "[X]"
+                } else {
+                    "[ ]"
+                };
+                let kind = if categories::is_main(option) {
+                    ""
+                } else {
+                    "  (additional)"
+                };
+                ListItem::new(format!("{checked} {option}{kind}"))
+            })
+            .collect();
+
+        let mut state = ListState::default().with_selected(Some(self.picker_cursor));
+        frame.render_stateful_widget(
+            List::new(items).highlight_style(Style::default().fg(Color::LightGreen).bold()),
+            list_area,
+            &mut state,
+        );
+
+        let warning_lines: Vec<Line> = warnings
+            .iter()
+            .take(3)
+            .map(|w| Line::from(format!("⚠ {w}")))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(warning_lines).style(Style::default().fg(Color::Yellow)),
+            warning_area,
+        );
+
+        let cursor_x = search_area.x + 8 + self.picker_query.visual_cursor() as u16;
+        frame.set_cursor_position(Position::new(cursor_x, search_area.y));
+    }
+    fn draw_actions(&self, frame: &mut Frame, area: Rect) {
+        let warnings = actions::check(&self.actions);
+
+        let block = Block::bordered()
+            .title(" Desktop Actions ")
+            .title_bottom(Line::from(vec![
+                " Add ".into(),
+                "<C-N> ".white().bold(),
+                "─".into(),
+                " Delete ".into(),
+                "<C-D> ".white().bold(),
+                "─".into(),
+                " Field ".into(),
+                "<Tab> ".white().bold(),
+                "─".into(),
+                " Done ".into(),
+                "<Enter> ".white().bold(),
+            ]))
+            .border_style(Style::default().fg(Color::LightGreen));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let [body_area, warning_area] = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(warnings.len().min(3) as u16),
+        ])
+        .areas(inner);
+        let [list_area, fields_area] =
+            Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)])
+                .areas(body_area);
+
+        let items: Vec<ListItem> = self
+            .actions
+            .iter()
+            .enumerate()
+            .map(|(i, action)| {
+                let label = if action.id.is_empty() {
+                    format!("<action {}>", i + 1)
+                } else {
+                    action.id.clone()
+                };
+                ListItem::new(label)
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(self.action_selected));
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(Block::default().borders(Borders::RIGHT))
+                .highlight_style(Style::default().fg(Color::LightGreen).bold())
+                .highlight_symbol("> "),
+            list_area,
+            &mut state,
+        );
+
+        let selected = self.actions.get(self.action_selected);
+        let field_areas = Layout::vertical([Constraint::Length(2); 4]).split(fields_area);
+        for (i, label) in ACTION_FIELDS.iter().enumerate() {
+            let value = if i == self.action_field {
+                self.action_input.value().to_string()
+            } else {
+                selected
+                    .map(|a| match i {
+                        0 => escape(&a.id),
+                        1 => escape(&a.name),
+                        2 => escape(&a.exec),
+                        _ => escape(&a.icon),
+                    })
+                    .unwrap_or_default()
+            };
+            let style = if i == self.action_field {
+                Style::default().fg(Color::LightGreen)
+            } else {
+                Style::default()
+            };
+            frame.render_widget(
+                Paragraph::new(format!(" {label}: [ {value} ]"))
+                    .style(style)
+                    .add_modifier(Modifier::BOLD),
+                field_areas[i],
+            );
+        }
+
+        let warning_lines: Vec<Line> = warnings
+            .iter()
+            .take(3)
+            .map(|w| Line::from(format!("⚠ {w}")))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(warning_lines).style(Style::default().fg(Color::Yellow)),
+            warning_area,
+        );
+
+        let label_len = ACTION_FIELDS[self.action_field].len() as u16;
+        let field_area = field_areas[self.action_field];
+        let cursor_x = field_area.x + label_len + 5 + self.action_input.visual_cursor() as u16;
+        frame.set_cursor_position(Position::new(
+            cursor_x.min(field_area.right().saturating_sub(1)),
+            field_area.y,
+        ));
+    }
+    /// The locales `key` is translated to, as shown next to its field.
+    fn translated_label(&self, key: &str) -> String {
+        match self.translated_locales(key).as_slice() {
+            [] => String::new(),
+            locales => format!(" [{}]", locales.join(", ")),
         }
     }
+    fn draw_translations(&self, frame: &mut Frame, area: Rect) {
+        let panel = self.panel_translations();
+        let shown: Vec<_> = panel
+            .iter()
+            .map(|&i| self.translations[i].clone())
+            .collect();
+        let warnings = locale::check(&shown);
+
+        let block = Block::bordered()
+            .title(format!(" Translations of {} ", self.translation_key))
+            .title_bottom(Line::from(vec![
+                " Add ".into(),
+                "<C-N> ".white().bold(),
+                "─".into(),
+                " Delete ".into(),
+                "<C-D> ".white().bold(),
+                "─".into(),
+                " Field ".into(),
+                "<Tab> ".white().bold(),
+                "─".into(),
+                " Done ".into(),
+                "<Enter> ".white().bold(),
+            ]))
+            .border_style(Style::default().fg(Color::LightGreen));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let [body_area, warning_area] = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(warnings.len().min(3) as u16),
+        ])
+        .areas(inner);
+        let [list_area, fields_area] =
+            Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)])
+                .areas(body_area);
+
+        let items: Vec<ListItem> = shown
+            .iter()
+            .enumerate()
+            .map(|(i, translation)| {
+                let label = if translation.locale.is_empty() {
+                    format!("<translation {}>", i + 1)
+                } else {
+                    translation.locale.clone()
+                };
+                ListItem::new(label)
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(self.translation_selected));
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(Block::default().borders(Borders::RIGHT))
+                .highlight_style(Style::default().fg(Color::LightGreen).bold())
+                .highlight_symbol("> "),
+            list_area,
+            &mut state,
+        );
+
+        let selected = shown.get(self.translation_selected);
+        let field_areas = Layout::vertical([Constraint::Length(2); 2]).split(fields_area);
+        for (i, label) in TRANSLATION_FIELDS.iter().enumerate() {
+            let value = if i == self.translation_field {
+                self.translation_input.value().to_string()
+            } else {
+                selected
+                    .map(|t| match i {
+                        0 => t.locale.clone(),
+                        _ => t.value.clone(),
+                    })
+                    .unwrap_or_default()
+            };
+            let style = if i == self.translation_field {
+                Style::default().fg(Color::LightGreen)
+            } else {
+                Style::default()
+            };
+            frame.render_widget(
+                Paragraph::new(format!(" {label}: [ {value} ]"))
+                    .style(style)
+                    .add_modifier(Modifier::BOLD),
+                field_areas[i],
+            );
+        }
+
+        let warning_lines: Vec<Line> = warnings
+            .iter()
+            .take(3)
+            .map(|w| Line::from(format!("⚠ {w}")))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(warning_lines).style(Style::default().fg(Color::Yellow)),
+            warning_area,
+        );
+
+        let label_len = TRANSLATION_FIELDS[self.translation_field].len() as u16;
+        let field_area = field_areas[self.translation_field];
+        let cursor_x = field_area.x + label_len + 5 + self.translation_input.visual_cursor() as u16;
+        frame.set_cursor_position(Position::new(
+            cursor_x.min(field_area.right().saturating_sub(1)),
+            field_area.y,
+        ));
+    }
+}
+
+/// Colors a line of a desktop file: comments, group headers, and keys with
+/// their locale apart from values.
+fn highlight(line: &str) -> Vec<Span<'_>> {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return vec![Span::styled(line, Style::default().fg(Color::DarkGray))];
+    }
+    if trimmed.starts_with('[') {
+        return vec![Span::styled(
+            line,
+            Style::default().fg(Color::LightBlue).bold(),
+        )];
+    }
+    let Some((key, value)) = line.split_once('=') else {
+        return vec![Span::raw(line)];
+    };
+
+    let (name, locale) = split_locale(key.trim_end());
+    let mut spans = vec![Span::styled(name, Style::default().fg(Color::LightCyan))];
+    if let Some(locale) = locale {
+        spans.push(Span::styled(
+            format!("[{locale}]"),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    spans.push(Span::raw(&key[key.trim_end().len()..]));
+    spans.push(Span::styled("=", Style::default().fg(Color::DarkGray)));
+    spans.push(Span::raw(value));
+    spans
 }
@@ -0,0 +1,56 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// A temporary file next to `path`, so renaming it over `path` never crosses
+/// a filesystem boundary.
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{name}.{}.tmp", std::process::id()))
+}
+
+fn write_temp(path: &Path, contents: &str) -> io::Result<PathBuf> {
+    let temp = temp_path(path);
+    let result = File::create(&temp).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    });
+    match result {
+        Ok(()) => Ok(temp),
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+/// Replaces `path` with `contents` in one step: readers see either the old
+/// file or the new one, never a partial write.
+pub fn write(path: &Path, contents: &str) -> io::Result<()> {
+    let temp = write_temp(path, contents)?;
+    fs::rename(&temp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp);
+    })
+}
+
+/// Like [`write`], but fails with `AlreadyExists` instead of replacing an
+/// existing file.
+pub fn create(path: &Path, contents: &str) -> io::Result<()> {
+    let temp = write_temp(path, contents)?;
+    let result = fs::hard_link(&temp, path);
+    let _ = fs::remove_file(&temp);
+    result
+}
+
+/// Renames `from` to `to`, failing with `AlreadyExists` instead of replacing
+/// an existing `to`. The file is linked under the new name before the old
+/// one is removed, so it is never missing under both.
+pub fn rename(from: &Path, to: &Path) -> io::Result<()> {
+    fs::hard_link(from, to)?;
+    fs::remove_file(from).inspect_err(|_| {
+        let _ = fs::remove_file(to);
+    })
+}
@@ -0,0 +1,96 @@
+use crate::app::state::InputMode;
+use crate::browser::state::Browser;
+
+use color_eyre::eyre::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use color_eyre::eyre::Ok;
+
+use tui_input::backend::crossterm::EventHandler;
+
+impl Browser {
+    pub fn handle_event(&mut self) -> Result<()> {
+        if let Event::Key(key_event) = event::read()?
+            && key_event.kind == KeyEventKind::Press
+        {
+            self.handle_key_input(key_event);
+        }
+        Ok(())
+    }
+
+    fn handle_key_input(&mut self, key_event: KeyEvent) {
+        match self.input_mode {
+            InputMode::Insert if self.prompt.is_some() => self.handle_prompt_key(key_event),
+            InputMode::Normal => self.handle_normal_key(key_event),
+            InputMode::Insert => self.handle_search_key(key_event),
+        }
+    }
+
+    fn handle_normal_key(&mut self, key_event: KeyEvent) {
+        let last_key = self.last_key.take();
+        self.message = None;
+
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.exit();
+            }
+            KeyCode::Char('q') => self.exit(),
+
+            // Vim keys
+            KeyCode::Char('j') | KeyCode::Down => self.next(),
+            KeyCode::Char('k') | KeyCode::Up => self.previous(),
+            KeyCode::Char('g') => match last_key {
+                Some(KeyCode::Char('g')) => self.first(),
+                _ => self.last_key = Some(KeyCode::Char('g')),
+            },
+            KeyCode::Char('G') => self.last(),
+            KeyCode::Char('d') => match last_key {
+                Some(KeyCode::Char('d')) => self.delete_selected(),
+                _ => self.last_key = Some(KeyCode::Char('d')),
+            },
+            KeyCode::Char('y') => match last_key {
+                Some(KeyCode::Char('y')) => self.start_copy(),
+                _ => self.last_key = Some(KeyCode::Char('y')),
+            },
+            KeyCode::Char('/') => self.input_mode = InputMode::Insert,
+
+            KeyCode::Enter => self.open_selected(),
+            KeyCode::Char('n') => self.new_launcher(),
+            KeyCode::Char('o') => self.override_selected(),
+            KeyCode::Char('r') => self.start_rename(),
+            KeyCode::Esc => {
+                self.query.reset();
+                self.refilter();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_prompt_key(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Enter => self.submit_prompt(),
+            KeyCode::Esc => self.close_prompt(),
+            _ => {
+                self.prompt_input.handle_event(&Event::Key(key_event));
+            }
+        }
+    }
+
+    fn handle_search_key(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Enter => self.input_mode = InputMode::Normal,
+            KeyCode::Esc => {
+                self.query.reset();
+                self.refilter();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Down => self.next(),
+            KeyCode::Up => self.previous(),
+            _ => {
+                self.query.handle_event(&Event::Key(key_event));
+                self.selected = 0;
+                self.refilter();
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod event;
+pub mod state;
+pub mod ui;
+
+pub use state::Browser;
@@ -0,0 +1,366 @@
+use crate::app::{App, state::InputMode};
+use crate::commands::{overrides, rename};
+
+use deskforge::desktop::locale;
+use deskforge::fuzzy;
+use deskforge::launchers::{self, Launcher};
+use deskforge::trash::Trash;
+use deskforge::xdg;
+
+use ratatui::crossterm::event::KeyCode;
+use tui_input::Input;
+
+use std::fs::create_dir_all;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// The home screen: every launcher the menus know about, searchable.
+#[derive(Debug)]
+pub struct Browser {
+    pub input_mode: InputMode,
+    pub last_key: Option<KeyCode>,
+
+    /// Every launcher found, shadowed ones included.
+    pub launchers: Vec<Launcher>,
+    /// Indices into `launchers` matching the query, best match first.
+    pub matches: Vec<usize>,
+    pub selected: usize,
+    pub query: Input,
+    /// Names and comments are shown and searched in this locale.
+    pub locale: Option<String>,
+
+    /// Asking for a desktop file ID to copy or rename to.
+    pub prompt: Option<Prompt>,
+    pub prompt_input: Input,
+
+    /// Feedback for the last action, shown below the list.
+    pub message: Option<String>,
+    /// The editor to show next, opened by the run loop.
+    pub form: Option<App>,
+    pub user_dir: PathBuf,
+    pub exit: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prompt {
+    Copy,
+    Rename,
+}
+
+impl Browser {
+    pub fn new() -> Self {
+        let user_dir = xdg::applications_home();
+        // Errors show up when something is written there.
+        let _ = create_dir_all(&user_dir);
+
+        let mut browser = Self {
+            input_mode: InputMode::Normal,
+            last_key: None,
+
+            launchers: Vec::new(),
+            matches: Vec::new(),
+            selected: 0,
+            query: Input::default(),
+            locale: locale::current(),
+
+            prompt: None,
+            prompt_input: Input::default(),
+
+            message: None,
+            form: None,
+            user_dir,
+            exit: false,
+        };
+        browser.reload();
+        browser
+    }
+
+    /// Scans the launcher directories again, keeping the selection.
+    pub fn reload(&mut self) {
+        let selected = self.selected().map(|l| l.id.clone());
+        self.launchers = launchers::scan_all();
+        self.refilter();
+        if let Some(id) = selected {
+            self.select_id(&id);
+        }
+    }
+
+    /// Ranks the launchers against the query by Name, Exec and Comment.
+    /// Without a query they are sorted by Name.
+    pub fn refilter(&mut self) {
+        let query = self.query.value();
+        let locale = self.locale.as_deref();
+        let mut scored: Vec<(i64, usize)> = self
+            .launchers
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| l.shadowed_by.is_none())
+            .filter_map(|(i, l)| {
+                let score = [
+                    l.localized("Name", locale),
+                    l.target(),
+                    l.localized("Comment", locale),
+                ]
+                .iter()
+                .filter_map(|text| fuzzy::score(query, text))
+                .max()?;
+                Some((score, i))
+            })
+            .collect();
+
+        scored.sort_by(|(a_score, a), (b_score, b)| {
+            let (a, b) = (&self.launchers[*a], &self.launchers[*b]);
+            b_score
+                .cmp(a_score)
+                .then_with(|| {
+                    let name = |l: &Launcher| l.localized("Name", locale).to_lowercase();
+                    name(a).cmp(&name(b))
+                })
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        self.selected = self.selected.min(self.matches.len().saturating_sub(1));
+    }
+
+    pub fn selected(&self) -> Option<&Launcher> {
+        self.matches.get(self.selected).map(|&i| &self.launchers[i])
+    }
+
+    fn select_id(&mut self, id: &str) {
+        if let Some(position) = self
+            .matches
+            .iter()
+            .position(|&i| self.launchers[i].id == id)
+        {
+            self.selected = position;
+        }
+    }
+
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.matches.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn first(&mut self) {
+        self.selected = 0;
+    }
+
+    pub fn last(&mut self) {
+        self.selected = self.matches.len().saturating_sub(1);
+    }
+
+    /// Launchers in the user's directory can be changed in place.
+    pub fn is_user(&self, launcher: &Launcher) -> bool {
+        launcher.path.starts_with(&self.user_dir)
+    }
+
+    /// Whether `launcher` hides a system launcher with the same ID.
+    pub fn is_override(&self, launcher: &Launcher) -> bool {
+        self.launchers
+            .iter()
+            .any(|l| l.shadowed_by.as_ref() == Some(&launcher.path))
+    }
+
+    /// Opens the selected launcher in the editor. Saving a system launcher
+    /// writes an override under the same ID. The editor saves directly in
+    /// the user's directory, so user launchers in a subdirectory of it are
+    /// refused rather than saved a second time under the same ID, and so
+    /// are files that can't be parsed.
+    pub fn open_selected(&mut self) {
+        let Some(launcher) = self.selected() else {
+            return;
+        };
+        if self.is_user(launcher) && launcher.path.parent() != Some(self.user_dir.as_path()) {
+            self.message = Some(format!(
+                "{} is in a subdirectory, which the editor can't save to",
+                launcher.id
+            ));
+            return;
+        }
+        match App::open(&launcher.path, &launcher.id) {
+            Ok(form) => self.form = Some(form),
+            Err(e) => self.message = Some(format!("Can't open {}: {e}", launcher.id)),
+        }
+    }
+
+    pub fn new_launcher(&mut self) {
+        self.form = Some(App::new(None));
+    }
+
+    /// Moves the selected launcher to the trash. System launchers can only
+    /// be overridden or hidden.
+    pub fn delete_selected(&mut self) {
+        let Some(launcher) = self.selected() else {
+            return;
+        };
+        if !self.is_user(launcher) {
+            self.message = Some(format!("{} is a system launcher", launcher.id));
+            return;
+        }
+
+        let (id, path) = (launcher.id.clone(), launcher.path.clone());
+        self.message = Some(match Trash::home().put(&path) {
+            Ok(_) => format!("Moved {id} to the trash"),
+            Err(e) => format!("Can't remove {id}: {e}"),
+        });
+        self.reload();
+    }
+
+    /// Asks for the ID of a copy of the selected launcher, suggesting the
+    /// first free `<id>-copy.desktop`.
+    pub fn start_copy(&mut self) {
+        let Some(launcher) = self.selected() else {
+            return;
+        };
+        let stem = launcher.id.trim_end_matches(".desktop");
+        let suggestion = (1..)
+            .map(|n| match n {
+                1 => format!("{stem}-copy.desktop"),
+                n => format!("{stem}-copy-{n}.desktop"),
+            })
+            .find(|id| !self.user_dir.join(id).exists())
+            .unwrap_or_default();
+        self.open_prompt(Prompt::Copy, suggestion);
+    }
+
+    /// Asks for a new ID for the selected launcher. System launchers can
+    /// only be copied.
+    pub fn start_rename(&mut self) {
+        let Some(launcher) = self.selected() else {
+            return;
+        };
+        if !self.is_user(launcher) {
+            self.message = Some(format!("{} is a system launcher, copy it", launcher.id));
+            return;
+        }
+        let id = launcher.id.clone();
+        self.open_prompt(Prompt::Rename, id);
+    }
+
+    fn open_prompt(&mut self, prompt: Prompt, value: String) {
+        self.prompt = Some(prompt);
+        self.prompt_input = Input::from(value);
+        self.input_mode = InputMode::Insert;
+    }
+
+    pub fn close_prompt(&mut self) {
+        self.prompt = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Copies or renames the selected launcher to the ID typed in.
+    pub fn submit_prompt(&mut self) {
+        let Some(prompt) = self.prompt else {
+            return;
+        };
+        let Some(launcher) = self.selected() else {
+            self.close_prompt();
+            return;
+        };
+        let (old_id, path) = (launcher.id.clone(), launcher.path.clone());
+        let name = launcher.name().to_string();
+
+        let id = match rename::check_id(self.prompt_input.value()) {
+            Ok(id) => id,
+            Err(e) => {
+                self.message = Some(e);
+                return;
+            }
+        };
+
+        let result = match prompt {
+            Prompt::Copy => {
+                let name = (!name.is_empty()).then(|| format!("{name} (Copy)"));
+                rename::copy_to(&path, &id, name.as_deref())
+            }
+            Prompt::Rename => rename::move_to(&path, &id),
+        };
+        match result {
+            Ok(_) => {
+                let done = match prompt {
+                    Prompt::Copy => format!("Copied {old_id} to {id}"),
+                    Prompt::Rename => format!("Renamed {old_id} to {id}"),
+                };
+                let warnings = match prompt {
+                    Prompt::Copy => Vec::new(),
+                    Prompt::Rename => rename::stale_references(&old_id),
+                };
+                self.message = Some(with_warnings(done, &warnings));
+                self.close_prompt();
+                self.reload();
+                self.select_id(&id);
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                self.message = Some(format!("{id} already exists"));
+            }
+            Err(e) => {
+                self.message = Some(format!("Can't write {id}: {e}"));
+                self.close_prompt();
+            }
+        }
+    }
+
+    /// Copies the selected system launcher unchanged into the user's
+    /// directory and opens the copy, like `deskforge override`.
+    pub fn override_selected(&mut self) {
+        let Some(launcher) = self.selected() else {
+            return;
+        };
+        if self.is_user(launcher) {
+            self.message = Some(format!("{} is not a system launcher", launcher.id));
+            return;
+        }
+
+        let id = launcher.id.clone();
+        // The copy is byte for byte, so the form can come from the system
+        // file, and a file the editor can't open is never copied.
+        let form = match App::open(&launcher.path, &id) {
+            Ok(form) => form,
+            Err(e) => {
+                self.message = Some(format!("Can't open {id}: {e}"));
+                return;
+            }
+        };
+        let target = match overrides::copy_system(&id) {
+            Ok(target) => target,
+            Err(e) => {
+                self.message = Some(format!("Can't override {id}: {e}"));
+                return;
+            }
+        };
+
+        self.message = Some(format!("Copied {id} to {}", target.display()));
+        self.form = Some(form);
+    }
+
+    /// Picks the list up again after the editor closed, showing what
+    /// still uses the old ID when saving renamed the launcher.
+    pub fn form_closed(&mut self, form: &App) {
+        self.reload();
+        if !form.warnings.is_empty() {
+            let done = format!("Saved {}", form.desktop_id());
+            self.message = Some(with_warnings(done, &form.warnings));
+            self.select_id(&form.desktop_id());
+        }
+    }
+
+    pub fn exit(&mut self) {
+        self.exit = true;
+    }
+}
+
+/// `done`, followed by the first of `warnings` and how many more there are.
+fn with_warnings(done: String, warnings: &[String]) -> String {
+    match warnings.first() {
+        None => done,
+        Some(first) if warnings.len() > 1 => {
+            format!("{done} ⚠ {first} (+{} more)", warnings.len() - 1)
+        }
+        Some(first) => format!("{done} ⚠ {first}"),
+    }
+}
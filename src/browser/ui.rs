@@ -0,0 +1,233 @@
+use crate::app::state::InputMode;
+use crate::browser::state::{Browser, Prompt};
+use crate::utils::constants::*;
+
+use color_eyre::eyre::{Ok, Result};
+use deskforge::desktop::lint::Severity;
+use deskforge::launchers::{Launcher, Status};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Position},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Row, Table, TableState},
+};
+
+impl Browser {
+    pub fn run(&mut self, terminal: &mut ratatui::DefaultTerminal) -> Result<()> {
+        while !self.exit {
+            terminal.draw(|frame| self.draw(frame))?;
+            self.handle_event()?;
+
+            if let Some(mut form) = self.form.take() {
+                form.run(terminal)?;
+                self.form_closed(&form);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let frame_width = frame.area().width;
+        let frame_height = frame.area().height;
+
+        if frame_width < SMALLEST_WIDTH || frame_height < SMALLEST_HEIGHT {
+            let current = Paragraph::new(format!(
+                "Terminal size is too small:\n Width: {frame_width} Height: {frame_height}\n\nNeeded terminal size:\n Width: {SMALLEST_WIDTH} Height: {SMALLEST_HEIGHT}"
+            ))
+            .centered();
+            frame.render_widget(current, frame.area());
+            return;
+        }
+
+        let mode = match self.input_mode {
+            InputMode::Normal => vec!["Mode: ".into(), "NORMAL".white().bold()],
+            InputMode::Insert if self.prompt.is_some() => {
+                vec!["Mode: ".into(), "INSERT".white().bold()]
+            }
+            InputMode::Insert => vec!["Mode: ".into(), "SEARCH".white().bold()],
+        };
+        let navigation = Line::from(vec![
+            " Open ".into(),
+            "<Enter> ".white().bold(),
+            "─".into(),
+            " New ".into(),
+            "<N> ".white().bold(),
+            "─".into(),
+            " Search ".into(),
+            "</> ".white().bold(),
+            "─".into(),
+            " Quit ".into(),
+            "<Q> ".white().bold(),
+        ]);
+        let actions = Line::from(vec![
+            " Delete ".into(),
+            "<DD> ".white().bold(),
+            "─".into(),
+            " Copy ".into(),
+            "<YY> ".white().bold(),
+            "─".into(),
+            " Rename ".into(),
+            "<R> ".white().bold(),
+            "─".into(),
+            " Override ".into(),
+            "<O> ".white().bold(),
+        ])
+        .right_aligned();
+
+        let outline_block = Block::bordered()
+            .title(mode)
+            .title(" DeskForge - Launchers ".bold().into_centered_line())
+            .title_bottom(navigation)
+            .title_bottom(actions);
+        let inner = outline_block.inner(frame.area());
+        frame.render_widget(outline_block, frame.area());
+
+        let [search_area, list_area, details_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(4),
+        ])
+        .areas(inner);
+
+        if let Some(prompt) = self.prompt {
+            let label = match prompt {
+                Prompt::Copy => " Copy to: ",
+                Prompt::Rename => " Rename to: ",
+            };
+            frame.render_widget(
+                Paragraph::new(format!("{label}{}", self.prompt_input.value()))
+                    .style(Style::default().fg(Color::LightGreen)),
+                search_area,
+            );
+            let cursor_x =
+                search_area.x + label.len() as u16 + self.prompt_input.visual_cursor() as u16;
+            frame.set_cursor_position(Position::new(
+                cursor_x.min(search_area.right().saturating_sub(1)),
+                search_area.y,
+            ));
+        }
+
+        let search_style = match self.input_mode {
+            InputMode::Insert => Style::default().fg(Color::LightGreen),
+            InputMode::Normal => Style::default(),
+        };
+        let count = format!("  ({}/{})", self.matches.len(), self.visible_count());
+        if self.prompt.is_none() {
+            frame.render_widget(
+                Paragraph::new(Line::from(vec![
+                    Span::styled(format!(" Search: {}", self.query.value()), search_style),
+                    Span::styled(count, Style::default().fg(Color::DarkGray)),
+                ])),
+                search_area,
+            );
+        }
+
+        let rows: Vec<Row> = self
+            .matches
+            .iter()
+            .map(|&i| {
+                let launcher = &self.launchers[i];
+                let (symbol, color) = status_symbol(launcher.status());
+                let name = match launcher.localized("Name", self.locale.as_deref()) {
+                    "" => launcher.id.trim_end_matches(".desktop"),
+                    name => name,
+                };
+                let source = if !self.is_user(launcher) {
+                    "system"
+                } else if self.is_override(launcher) {
+                    "override"
+                } else {
+                    "user"
+                };
+                Row::new(vec![
+                    Span::styled(symbol, Style::default().fg(color)),
+                    name.into(),
+                    Span::styled(launcher.id.as_str(), Style::default().fg(Color::DarkGray)),
+                    source.into(),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(1),
+                Constraint::Percentage(45),
+                Constraint::Percentage(45),
+                Constraint::Length(8),
+            ],
+        )
+        .row_highlight_style(Style::default().fg(Color::LightGreen).bold())
+        .highlight_symbol("> ");
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, list_area, &mut state);
+
+        frame.render_widget(self.details(), details_area);
+
+        if self.input_mode == InputMode::Insert && self.prompt.is_none() {
+            let cursor_x = search_area.x + 9 + self.query.visual_cursor() as u16;
+            frame.set_cursor_position(Position::new(
+                cursor_x.min(search_area.right().saturating_sub(1)),
+                search_area.y,
+            ));
+        }
+    }
+
+    fn visible_count(&self) -> usize {
+        self.launchers
+            .iter()
+            .filter(|l| l.shadowed_by.is_none())
+            .count()
+    }
+
+    /// Path and check results of the selected launcher, or the message
+    /// left by the last action.
+    fn details(&self) -> Paragraph<'_> {
+        let Some(launcher) = self.selected() else {
+            return Paragraph::new(self.message.clone().unwrap_or_default())
+                .block(Block::bordered());
+        };
+
+        let mut lines = vec![Line::from(launcher.path.display().to_string())];
+        match &self.message {
+            Some(message) => lines.push(Line::from(message.as_str()).bold()),
+            None => lines.push(diagnostics_summary(launcher)),
+        }
+        Paragraph::new(lines)
+            .block(Block::bordered().title(launcher.entry_type().to_string()))
+            .add_modifier(Modifier::BOLD)
+    }
+}
+
+fn status_symbol(status: Status) -> (&'static str, Color) {
+    match status {
+        Status::Valid => ("✓", Color::LightGreen),
+        Status::Warnings => ("!", Color::Yellow),
+        Status::Invalid => ("✗", Color::LightRed),
+    }
+}
+
+/// "2 errors, 1 warning - <first problem>", or that the launcher is valid.
+fn diagnostics_summary(launcher: &Launcher) -> Line<'_> {
+    let (_, color) = status_symbol(launcher.status());
+    let errors = launcher.count(Severity::Error);
+    let warnings = launcher.count(Severity::Warning);
+    let first = launcher
+        .diagnostics
+        .iter()
+        .find(|d| d.severity != Severity::Hint);
+
+    let text = match first {
+        None => "Valid".to_string(),
+        Some(first) => format!(
+            "{errors} error{}, {warnings} warning{} - {}",
+            if errors == 1 { "" } else { "s" },
+            if warnings == 1 { "" } else { "s" },
+            first.message
+        ),
+    };
+    Line::from(text).style(Style::default().fg(color))
+}
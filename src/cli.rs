@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(
@@ -8,6 +8,9 @@ use clap::Parser;
     override_usage = "deskforge [COMMANDS] [OPTIONS]"
 )]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Create a new launcher
     #[arg(short = 'n', long = "new", value_name = "OPTIONAL: FILE_NAME", num_args = 0..=1)]
     pub new: Option<Option<String>>,
@@ -15,12 +18,256 @@ pub struct Cli {
     /// Edit an existing launcher
     #[arg(short = 'e', long = "edit", value_name = "REQUIRED: FILE_NAME", num_args= 0..=1)]
     pub edit: Option<String>,
-    
-    /// Remove an exisiting launcher
-    #[arg(short = 'r', long = "remove", value_name = "REQUIRED: FILE_NAME", num_args= 0..=1)]
-    pub remove: Option<String>,
+
+    /// Move launchers to the trash, names may be globs like 'steam-*'
+    #[arg(short = 'r', long = "remove", value_name = "REQUIRED: FILE_NAME", num_args = 1..)]
+    pub remove: Option<Vec<String>>,
+
+    /// Don't ask before removing several launchers
+    #[arg(short = 'y', long, requires = "remove")]
+    pub yes: bool,
+
+    /// Check launchers against the Desktop Entry Specification
+    #[arg(short = 'c', long = "check", value_name = "REQUIRED: FILE_NAME", num_args = 1..)]
+    pub check: Option<Vec<String>>,
 
     /// List all exisiting launcher
     #[arg(short = 'l', long = "list")]
     pub list: bool,
+
+    /// List launchers from every XDG data directory, not just the user's
+    #[arg(short = 'a', long, requires = "list")]
+    pub all: bool,
+
+    /// Output format of --list
+    #[arg(long, value_enum, default_value_t = Format::Text, requires = "list")]
+    pub format: Format,
+
+    /// Sort --list by
+    #[arg(long, value_enum, default_value_t = SortKey::Path, requires = "list")]
+    pub sort: SortKey,
+
+    /// Only list launchers of this Type
+    #[arg(long = "type", value_name = "TYPE", requires = "list")]
+    pub entry_type: Option<String>,
+
+    /// Only list launchers in this category
+    #[arg(long, requires = "list")]
+    pub category: Option<String>,
+
+    /// Only list launchers hidden with NoDisplay or Hidden
+    #[arg(long, requires = "list", conflicts_with = "visible")]
+    pub hidden: bool,
+
+    /// Only list launchers shown in menus
+    #[arg(long, requires = "list")]
+    pub visible: bool,
+
+    /// Locale of the names in --list [default: $LANG]
+    #[arg(long, requires = "list")]
+    pub locale: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Tsv,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortKey {
+    Path,
+    Id,
+    Name,
+    Type,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Create a launcher without opening the editor
+    New(Box<NewArgs>),
+    /// Print the value of a key, e.g. "Name" or "Name[de]"
+    Get(GetArgs),
+    /// Change keys in place, leaving the rest of the file untouched
+    Set(SetArgs),
+    /// Copy a system launcher into your applications and edit it
+    Override(OverrideArgs),
+    /// Delete your override of a system launcher
+    Reset {
+        /// Desktop file ID, e.g. "firefox" or "org.gnome.Nautilus.desktop"
+        id: String,
+    },
+    /// Hide a launcher from menus without deleting it
+    Hide(HideArgs),
+    /// Show a hidden launcher in menus again
+    Unhide {
+        /// Desktop file ID, e.g. "firefox" or "org.gnome.Nautilus.desktop"
+        id: String,
+    },
+    /// Copy a launcher to a new desktop file ID
+    Cp(CopyArgs),
+    /// Change the desktop file ID of one of your launchers, keeping its Name
+    Mv(MoveArgs),
+    /// Bring back a removed launcher, or list removed launchers
+    Restore {
+        /// File name of the launcher
+        name: Option<String>,
+    },
+}
+
+#[derive(Args)]
+pub struct NewArgs {
+    /// File name of the launcher, ".desktop" is optional
+    #[arg(value_name = "FILE_NAME")]
+    pub file_name: String,
+
+    /// Name shown in menus [default: FILE_NAME]
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Application, Link or Directory
+    #[arg(long = "type", value_name = "TYPE", default_value = "Application")]
+    pub entry_type: String,
+
+    /// Command to run
+    #[arg(long)]
+    pub exec: Option<String>,
+
+    /// URL to open, for Type=Link
+    #[arg(long)]
+    pub url: Option<String>,
+
+    /// Icon name or path
+    #[arg(long)]
+    pub icon: Option<String>,
+
+    /// Version of the spec the file follows
+    #[arg(long = "spec-version", value_name = "VERSION")]
+    pub spec_version: Option<String>,
+
+    #[arg(long)]
+    pub comment: Option<String>,
+
+    /// Kind of program, e.g. "Web Browser"
+    #[arg(long)]
+    pub generic_name: Option<String>,
+
+    /// ";" separated search terms, e.g. "Internet;WWW"
+    #[arg(long)]
+    pub keywords: Option<String>,
+
+    /// ";" separated list, e.g. "Development;IDE"
+    #[arg(long)]
+    pub categories: Option<String>,
+
+    /// ";" separated MIME types the program opens, e.g. "text/html"
+    #[arg(long)]
+    pub mime_type: Option<String>,
+
+    /// Program that must exist for the launcher to be shown
+    #[arg(long)]
+    pub try_exec: Option<String>,
+
+    /// Working directory to run the program in
+    #[arg(long)]
+    pub path: Option<String>,
+
+    /// Window class the program's windows have, for grouping in docks
+    #[arg(long = "wm-class", value_name = "CLASS")]
+    pub startup_wm_class: Option<String>,
+
+    /// Run in a terminal
+    #[arg(long)]
+    pub terminal: bool,
+
+    /// Hide from menus
+    #[arg(long)]
+    pub no_display: bool,
+
+    /// Don't send startup notifications
+    #[arg(long)]
+    pub no_startup_notify: bool,
+
+    /// Overwrite an existing launcher
+    #[arg(short = 'f', long)]
+    pub force: bool,
+}
+
+#[derive(Args)]
+pub struct GetArgs {
+    /// Launcher name or path
+    pub launcher: String,
+
+    /// Key to read, with an optional [locale]
+    pub key: String,
+
+    /// Group to read from [default: "Desktop Entry"]
+    #[arg(long)]
+    pub group: Option<String>,
+
+    /// Locale to read a KEY without [locale] in, "C" for none [default: $LANG]
+    #[arg(long)]
+    pub locale: Option<String>,
+}
+
+#[derive(Args)]
+pub struct SetArgs {
+    /// Launcher name or path
+    pub launcher: String,
+
+    /// Values to set, written as in the file, e.g. "Categories=Development;IDE;"
+    #[arg(value_name = "KEY=VALUE")]
+    pub values: Vec<String>,
+
+    /// Remove a key, can be repeated
+    #[arg(long, value_name = "KEY")]
+    pub unset: Vec<String>,
+
+    /// Group to change, created if needed [default: "Desktop Entry"]
+    #[arg(long)]
+    pub group: Option<String>,
+}
+
+#[derive(Args)]
+pub struct OverrideArgs {
+    /// Desktop file ID, e.g. "firefox" or "org.gnome.Nautilus.desktop"
+    pub id: String,
+
+    /// Only copy the launcher, don't open the editor
+    #[arg(long)]
+    pub no_edit: bool,
+}
+
+#[derive(Args)]
+pub struct HideArgs {
+    /// Desktop file ID, e.g. "firefox" or "org.gnome.Nautilus.desktop"
+    pub id: String,
+
+    /// Set Hidden=true, which also hides it from file associations, instead
+    /// of NoDisplay=true
+    #[arg(long)]
+    pub hidden: bool,
+}
+
+#[derive(Args)]
+pub struct CopyArgs {
+    /// Desktop file ID of a user or system launcher, or a path
+    pub source: String,
+
+    /// New desktop file ID, e.g. "org.example.App"
+    pub dest: String,
+
+    /// Name of the copy shown in menus [default: unchanged]
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+#[derive(Args)]
+pub struct MoveArgs {
+    /// Desktop file ID of one of your launchers
+    pub source: String,
+
+    /// New desktop file ID, e.g. "org.example.App"
+    pub dest: String,
 }
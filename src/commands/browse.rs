@@ -0,0 +1,9 @@
+use crate::browser::Browser;
+use color_eyre::Result;
+
+pub fn browse() -> Result<()> {
+    let mut terminal = ratatui::init();
+    let result = Browser::new().run(&mut terminal);
+    ratatui::restore();
+    result
+}
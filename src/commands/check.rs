@@ -0,0 +1,51 @@
+use crate::commands::launcher_path;
+use deskforge::desktop::lint::{self, Severity};
+
+use std::fs::read_to_string;
+use std::path::Path;
+
+/// Prints every problem found in `path`. Returns whether the file is valid,
+/// i.e. has no errors.
+fn check_file(path: &Path) -> bool {
+    let content = match read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("[ERROR]: {}: {e}", path.display());
+            return false;
+        }
+    };
+
+    let diagnostics = lint::lint(&content);
+    for diagnostic in &diagnostics {
+        println!("{}:{diagnostic}", path.display());
+    }
+
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    };
+    if diagnostics.is_empty() {
+        println!("{}: OK", path.display());
+    } else {
+        println!(
+            "{}: {} error(s), {} warning(s), {} hint(s)",
+            path.display(),
+            count(Severity::Error),
+            count(Severity::Warning),
+            count(Severity::Hint)
+        );
+    }
+
+    !lint::has_errors(&diagnostics)
+}
+
+/// Lints every launcher in `names`. Returns whether all of them are valid.
+pub fn check(names: &[String]) -> bool {
+    let mut valid = true;
+    for name in names {
+        valid &= check_file(&launcher_path(name.trim()));
+    }
+    valid
+}
@@ -1,22 +1,33 @@
 use crate::App;
 use color_eyre::Result;
+use deskforge::desktop::{DesktopEntry, ParseError, id};
+
+use std::fs::read_to_string;
 
 pub fn edit_err(file_name: &str) -> bool {
-    let path = dirs::data_dir()
-        .unwrap()
-        .join("applications")
-        .join(file_name);
+    let path = id::user_path(file_name);
 
     if !path.exists() {
         return true;
     }
 
-    return false;
+    false
+}
+
+pub fn parse_err(file_name: &str) -> Option<ParseError> {
+    let path = id::user_path(file_name);
+
+    let content = read_to_string(&path).ok()?;
+    DesktopEntry::parse(&content).err()
 }
 
 pub fn edit(file_name: String) -> Result<()> {
+    let mut app = App::open(&id::user_path(&file_name), &file_name)?;
     let mut terminal = ratatui::init();
-    let result = App::new(Some(file_name), true).run(&mut terminal);
+    let result = app.run(&mut terminal);
     ratatui::restore();
-    return result;
+    for warning in &app.warnings {
+        eprintln!("[WARNING]: {warning}");
+    }
+    result
 }
@@ -0,0 +1,131 @@
+use crate::cli::HideArgs;
+use crate::commands::overrides::copy_to_user;
+use crate::utils::constants::*;
+use deskforge::atomic;
+use deskforge::desktop::id::{self, user_path};
+use deskforge::desktop::{DesktopEntry, Value};
+use deskforge::launchers::{self, locate};
+
+use std::fs::{read_to_string, remove_file};
+use std::path::{Path, PathBuf};
+
+/// The flags that keep a launcher out of menus.
+const FLAGS: [&str; 2] = ["NoDisplay", "Hidden"];
+
+fn load(path: &Path) -> Result<(String, DesktopEntry), i32> {
+    let content = read_to_string(path).map_err(|e| {
+        eprintln!("[ERROR]: {}: {e}", path.display());
+        EXIT_FAILURE
+    })?;
+    let entry = DesktopEntry::parse(&content).map_err(|e| {
+        eprintln!("[ERROR]: {}: {e}", path.display());
+        EXIT_INVALID
+    })?;
+    Ok((content, entry))
+}
+
+fn save(path: &Path, entry: &DesktopEntry) -> i32 {
+    match atomic::write(path, &entry.serialize()) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("[ERROR]: Can't save {}: {e}", path.display());
+            EXIT_FAILURE
+        }
+    }
+}
+
+/// The user's launcher `id`, copied from the system one if needed.
+fn user_launcher(id: &str) -> Result<PathBuf, i32> {
+    let path = user_path(id);
+    if path.exists() {
+        Ok(path)
+    } else {
+        copy_to_user(id)
+    }
+}
+
+/// Sets `NoDisplay=true`, or `Hidden=true` with `--hidden`, on the user's
+/// launcher. System launchers get a user override first.
+pub fn hide(args: HideArgs) -> i32 {
+    let id = id::normalize(&args.id);
+    let key = if args.hidden { "Hidden" } else { "NoDisplay" };
+
+    let path = match user_launcher(&id) {
+        Ok(path) => path,
+        Err(code) => return code,
+    };
+    let (_, mut entry) = match load(&path) {
+        Ok(loaded) => loaded,
+        Err(code) => return code,
+    };
+
+    entry.set(key, Value::Boolean(true));
+    let code = save(&path, &entry);
+    if code == 0 {
+        println!("[DESKFORGE]: {id} is hidden ({key}=true)");
+    }
+    code
+}
+
+/// Clears `NoDisplay` and `Hidden`. Keys the system launcher doesn't set are
+/// removed, and an override left identical to the system launcher is
+/// deleted.
+pub fn unhide(id: &str) -> i32 {
+    let id = id::normalize(id);
+    let system = locate(&id, &launchers::system_dirs());
+    let system_entry = match &system {
+        Some(path) => match load(path) {
+            Ok(loaded) => Some(loaded),
+            Err(code) => return code,
+        },
+        None => None,
+    };
+    let system_sets = |key: &str| {
+        system_entry
+            .as_ref()
+            .is_some_and(|(_, e)| e.get_bool(key) == Some(true))
+    };
+
+    if !user_path(&id).exists() && !FLAGS.iter().any(|key| system_sets(key)) {
+        if system.is_none() {
+            eprintln!("[ERROR]: No launcher with ID {id}");
+            return EXIT_FAILURE;
+        }
+        println!("[DESKFORGE]: {id} is not hidden");
+        return 0;
+    }
+
+    let path = match user_launcher(&id) {
+        Ok(path) => path,
+        Err(code) => return code,
+    };
+    let (_, mut entry) = match load(&path) {
+        Ok(loaded) => loaded,
+        Err(code) => return code,
+    };
+
+    for key in FLAGS {
+        if system_sets(key) {
+            entry.set(key, Value::Boolean(false));
+        } else if entry.get(key).is_some() {
+            entry.remove(key);
+        }
+    }
+
+    if let Some((content, _)) = &system_entry
+        && entry.serialize() == *content
+    {
+        if let Err(e) = remove_file(&path) {
+            eprintln!("[ERROR]: Can't remove {}: {e}", path.display());
+            return EXIT_FAILURE;
+        }
+        println!("[DESKFORGE]: {id} is visible, override removed");
+        return 0;
+    }
+
+    let code = save(&path, &entry);
+    if code == 0 {
+        println!("[DESKFORGE]: {id} is visible");
+    }
+    code
+}
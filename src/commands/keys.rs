@@ -0,0 +1,130 @@
+use crate::cli::{GetArgs, SetArgs};
+use crate::commands::launcher_path;
+use crate::utils::constants::*;
+use deskforge::atomic;
+use deskforge::desktop::entry::split_locale;
+use deskforge::desktop::lint::{self, Severity};
+use deskforge::desktop::parser::{is_valid_key, is_valid_locale};
+use deskforge::desktop::{DesktopEntry, MAIN_GROUP, Value, locale};
+
+use std::fs::read_to_string;
+use std::path::Path;
+
+/// Reads and parses a launcher, printing why it can't be used.
+fn load(path: &Path) -> Result<DesktopEntry, i32> {
+    let content = match read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("[ERROR]: {}: {e}", path.display());
+            return Err(EXIT_FAILURE);
+        }
+    };
+    DesktopEntry::parse(&content).map_err(|e| {
+        eprintln!("[ERROR]: {}: {e}", path.display());
+        EXIT_INVALID
+    })
+}
+
+/// Splits `Name[de]` and checks both parts.
+fn parse_key(full_key: &str) -> Option<(&str, Option<&str>)> {
+    let (key, locale) = split_locale(full_key.trim());
+    let valid = is_valid_key(key) && locale.is_none_or(is_valid_locale);
+    if !valid {
+        eprintln!("[ERROR]: Invalid key \"{full_key}\"");
+    }
+    valid.then_some((key, locale))
+}
+
+/// Prints one value. Strings are printed unescaped, everything else the
+/// way it is written in the file.
+pub fn get(args: GetArgs) -> i32 {
+    let path = launcher_path(&args.launcher);
+    let entry = match load(&path) {
+        Ok(entry) => entry,
+        Err(code) => return code,
+    };
+    let Some((key, locale)) = parse_key(&args.key) else {
+        return EXIT_FAILURE;
+    };
+
+    // A key without [locale] is read the way the user's desktop reads it.
+    let user_locale = match &args.locale {
+        Some(value) => locale::normalize(value),
+        None => locale::current(),
+    };
+    let group = args.group.as_deref().unwrap_or(MAIN_GROUP);
+    match entry.group(group).and_then(|g| match locale {
+        Some(_) => g.get_localized(key, locale),
+        None => g.get_for_locale(key, user_locale.as_deref()),
+    }) {
+        Some(Value::String(s)) => println!("{s}"),
+        Some(value) => println!("{value}"),
+        None => {
+            eprintln!("[ERROR]: No key \"{}\" in [{group}]", args.key);
+            return EXIT_FAILURE;
+        }
+    }
+    0
+}
+
+/// Applies `KEY=VALUE` assignments and `--unset` to one group. Only the
+/// lines of changed keys are rewritten.
+pub fn set(args: SetArgs) -> i32 {
+    let path = launcher_path(&args.launcher);
+    let mut entry = match load(&path) {
+        Ok(entry) => entry,
+        Err(code) => return code,
+    };
+    let group_name = args.group.as_deref().unwrap_or(MAIN_GROUP);
+    if group_name.is_empty()
+        || group_name.contains(['[', ']'])
+        || group_name.contains(char::is_control)
+    {
+        eprintln!("[ERROR]: Invalid group name \"{group_name}\"");
+        return EXIT_FAILURE;
+    }
+    let before = lint::check(&entry);
+
+    for full_key in &args.unset {
+        let Some((key, locale)) = parse_key(full_key) else {
+            return EXIT_FAILURE;
+        };
+        if let Some(group) = entry.group_mut(group_name) {
+            group.remove_localized(key, locale);
+        }
+    }
+
+    for assignment in &args.values {
+        let Some((full_key, raw)) = assignment.split_once('=') else {
+            eprintln!("[ERROR]: Expected KEY=VALUE, found \"{assignment}\"");
+            return EXIT_FAILURE;
+        };
+        let Some((key, locale)) = parse_key(full_key) else {
+            return EXIT_FAILURE;
+        };
+        entry
+            .group_or_insert(group_name)
+            .set_localized(key, locale, Value::parse(key, raw));
+    }
+
+    // Only refuse problems this change introduces, so a file that was
+    // already broken can still be fixed one key at a time.
+    let introduced: Vec<_> = lint::check(&entry)
+        .into_iter()
+        .filter(|d| d.severity == Severity::Error)
+        .filter(|d| !before.iter().any(|b| b.message == d.message))
+        .collect();
+    if !introduced.is_empty() {
+        for diagnostic in &introduced {
+            eprintln!("{}:{diagnostic}", path.display());
+        }
+        eprintln!("[ERROR]: Launcher would not be valid, nothing written");
+        return EXIT_INVALID;
+    }
+
+    if let Err(e) = atomic::write(&path, &entry.serialize()) {
+        eprintln!("[ERROR]: Can't save {}: {e}", path.display());
+        return EXIT_FAILURE;
+    }
+    0
+}
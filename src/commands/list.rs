@@ -1,36 +1,181 @@
-use std::fs::read_dir;
+use crate::cli::{Format, SortKey};
+use deskforge::desktop::lint::Severity;
+use deskforge::launchers::{self, Launcher};
+use deskforge::xdg;
 
-pub fn list_all_desktop_files() {
-    let mut counter: usize = 0;
-    let dir = dirs::data_dir().unwrap().join("applications");
+pub struct Filter {
+    pub entry_type: Option<String>,
+    pub category: Option<String>,
+    /// `Some(true)` keeps only hidden launchers, `Some(false)` only visible.
+    pub hidden: Option<bool>,
+}
 
-    if !dir.exists() {
-        eprintln!("[ERROR]: No applications directory found!");
-        return;
+impl Filter {
+    fn matches(&self, launcher: &Launcher) -> bool {
+        self.entry_type
+            .as_deref()
+            .is_none_or(|t| launcher.entry_type() == t)
+            && self
+                .category
+                .as_deref()
+                .is_none_or(|c| launcher.categories().iter().any(|x| x == c))
+            && self
+                .hidden
+                .is_none_or(|hidden| hidden == (launcher.no_display() || launcher.hidden()))
     }
+}
+
+/// Lists the user's launchers, or with `all` every launcher the menus know
+/// about, in order of precedence. Names are shown translated to `locale`.
+pub fn list_all_desktop_files(
+    all: bool,
+    format: Format,
+    sort: SortKey,
+    filter: &Filter,
+    locale: Option<&str>,
+) {
+    let found = if all {
+        launchers::scan_all()
+    } else {
+        let dir = xdg::applications_home();
 
-    let entries = match read_dir(&dir) {
-        std::result::Result::Ok(e) => e,
-        Err(err) => {
-            eprintln!("[ERROR]: Failed to read directory: {}", err);
+        if !dir.exists() {
+            eprintln!("[ERROR]: No applications directory found!");
             return;
         }
+        launchers::scan(&dir)
     };
 
+    let mut launchers: Vec<Launcher> = found.into_iter().filter(|l| filter.matches(l)).collect();
+    match sort {
+        SortKey::Path => {}
+        SortKey::Id => launchers.sort_by(|a, b| a.id.cmp(&b.id)),
+        SortKey::Name => launchers.sort_by_key(|l| l.localized("Name", locale).to_lowercase()),
+        SortKey::Type => launchers.sort_by(|a, b| a.entry_type().cmp(b.entry_type())),
+    }
+
+    match format {
+        Format::Text => print_text(&launchers, all),
+        Format::Json => println!("{}", to_json(&launchers, locale)),
+        Format::Tsv => print_tsv(&launchers, locale),
+    }
+}
+
+fn print_text(launchers: &[Launcher], all: bool) {
     println!("[DESKFORGE]");
-    for entry in entries {
-        if let std::result::Result::Ok(entry) = entry {
-            let path = entry.path();
-
-            if let Some(ext) = path.extension() {
-                if ext == "desktop" {
-                    if let Some(name) = path.file_name() {
-                        counter += 1;
-                        println!("{}. {}", counter, name.to_string_lossy());
-                    }
-                }
-            }
+    for (idx, launcher) in launchers.iter().enumerate() {
+        if !all {
+            println!("{}. {}", idx + 1, launcher.file_name());
+            continue;
         }
+        let shadowed = if launcher.shadowed_by.is_some() {
+            " [shadowed]"
+        } else {
+            ""
+        };
+        println!(
+            "{}. {} ({}){shadowed}",
+            idx + 1,
+            launcher.id,
+            launcher.path.display()
+        );
+    }
+    println!("Total: {}", launchers.len());
+}
+
+fn print_tsv(launchers: &[Launcher], locale: Option<&str>) {
+    println!(
+        "path\tid\tname\ttype\texec_or_url\ticon\tno_display\thidden\tcategories\tstatus\tshadowed"
+    );
+    for l in launchers {
+        let fields = [
+            l.path.display().to_string(),
+            l.id.clone(),
+            l.localized("Name", locale).to_string(),
+            l.entry_type().to_string(),
+            l.target().to_string(),
+            l.icon().to_string(),
+            l.no_display().to_string(),
+            l.hidden().to_string(),
+            l.categories().join(";"),
+            l.status().as_str().to_string(),
+            l.shadowed_by.is_some().to_string(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|f| tsv_escape(f)).collect();
+        println!("{}", fields.join("\t"));
+    }
+}
+
+fn tsv_escape(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// One JSON object per launcher.
+fn to_json(launchers: &[Launcher], locale: Option<&str>) -> String {
+    let objects: Vec<String> = launchers
+        .iter()
+        .map(|l| {
+            let categories: Vec<String> = l.categories().iter().map(|c| json_string(c)).collect();
+            let fields = [
+                ("path", json_string(&l.path.display().to_string())),
+                ("id", json_string(&l.id)),
+                ("name", json_string(l.localized("Name", locale))),
+                ("type", json_string(l.entry_type())),
+                (
+                    "exec",
+                    json_string(l.entry.get_str("Exec").unwrap_or_default()),
+                ),
+                (
+                    "url",
+                    json_string(l.entry.get_str("URL").unwrap_or_default()),
+                ),
+                ("icon", json_string(l.icon())),
+                ("no_display", l.no_display().to_string()),
+                ("hidden", l.hidden().to_string()),
+                ("categories", format!("[{}]", categories.join(", "))),
+                ("status", json_string(l.status().as_str())),
+                ("errors", l.count(Severity::Error).to_string()),
+                ("warnings", l.count(Severity::Warning).to_string()),
+                (
+                    "shadowed_by",
+                    l.shadowed_by.as_ref().map_or("null".to_string(), |p| {
+                        json_string(&p.display().to_string())
+                    }),
+                ),
+            ];
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("    \"{key}\": {value}"))
+                .collect();
+            format!("  {{\n{}\n  }}", fields.join(",\n"))
+        })
+        .collect();
+
+    if objects.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", objects.join(",\n"))
     }
-    println!("Total: {}", counter);
 }
@@ -1,4 +1,23 @@
+pub mod browse;
+pub mod check;
 pub mod edit;
+pub mod hide;
+pub mod keys;
 pub mod list;
 pub mod new;
-pub mod remove;
\ No newline at end of file
+pub mod overrides;
+pub mod remove;
+pub mod rename;
+
+use deskforge::desktop::id;
+
+use std::path::PathBuf;
+
+/// A launcher name is looked up in the applications directory; anything
+/// containing a `/` is taken as a path.
+pub fn launcher_path(name: &str) -> PathBuf {
+    if name.contains('/') {
+        return PathBuf::from(name);
+    }
+    id::user_path(name)
+}
@@ -1,18 +1,112 @@
 use crate::App;
+use crate::cli::NewArgs;
+use crate::utils::constants::*;
 use color_eyre::Result;
+use deskforge::atomic;
+use deskforge::desktop::Value;
+use deskforge::desktop::id;
+use deskforge::desktop::lint::{self, Severity};
+use deskforge::desktop::value::split_list;
+
+use std::io::ErrorKind;
+use std::path::Path;
 
 pub fn new_default_file() -> Result<()> {
     let default_name = "".to_string();
 
     let mut terminal = ratatui::init();
-    let result = App::new(Some(default_name), false).run(&mut terminal);
+    let result = App::new(Some(default_name)).run(&mut terminal);
     ratatui::restore();
-    return result;
+    result
 }
 
 pub fn new_file(name: Option<String>) -> Result<()> {
     let mut terminal = ratatui::init();
-    let result = App::new(name, false).run(&mut terminal);
+    let result = App::new(name).run(&mut terminal);
     ratatui::restore();
-    return result;
+    result
+}
+
+/// Fills the editor's form from the command line, checks the result with the
+/// same rules as the editor and writes it to `path`. Returns the exit code.
+pub fn new_headless(args: NewArgs, path: &Path) -> i32 {
+    if let Some(error) = id::check(&args.file_name)
+        .into_iter()
+        .find(|d| d.severity == Severity::Error)
+    {
+        eprintln!("[ERROR]: {}: {}", args.file_name, error.message);
+        return EXIT_INVALID;
+    }
+
+    let name = args
+        .file_name
+        .trim()
+        .trim_end_matches(".desktop")
+        .to_string();
+    let mut app = App::new(Some(name));
+
+    app.set_value("Type", &args.entry_type);
+    let (target_key, target) = if args.entry_type == "Link" {
+        ("URL", args.url)
+    } else {
+        ("Exec", args.exec)
+    };
+    for (key, value) in [
+        (target_key, target),
+        ("Icon", args.icon),
+        ("Version", args.spec_version),
+        ("Comment", args.comment),
+        ("GenericName", args.generic_name),
+        ("Keywords", args.keywords),
+        ("MimeType", args.mime_type),
+        ("TryExec", args.try_exec),
+        ("Path", args.path),
+        ("StartupWMClass", args.startup_wm_class),
+    ] {
+        if let Some(value) = value {
+            app.set_value(key, &value);
+        }
+    }
+    app.categories = args
+        .categories
+        .as_deref()
+        .map(split_list)
+        .unwrap_or_default();
+    app.set_value("Terminal", &args.terminal.to_string());
+    app.set_value("NoDisplay", &args.no_display.to_string());
+    app.set_value("StartupNotify", &(!args.no_startup_notify).to_string());
+
+    let mut entry = app.to_desktop_entry();
+    if let Some(name) = args.name {
+        entry.set("Name", Value::String(name));
+    }
+
+    let diagnostics = lint::check(&entry);
+    for diagnostic in &diagnostics {
+        eprintln!("{}:{diagnostic}", path.display());
+    }
+    if lint::has_errors(&diagnostics) {
+        eprintln!("[ERROR]: Launcher is not valid, nothing written");
+        return EXIT_INVALID;
+    }
+
+    let result = if args.force {
+        atomic::write(path, &entry.serialize())
+    } else {
+        atomic::create(path, &entry.serialize())
+    };
+    match result {
+        Ok(()) => {
+            println!("[DESKFORGE]: Created {}", path.display());
+            0
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            eprintln!("[ERROR]: File name already exists! Use --force to overwrite");
+            EXIT_EXISTS
+        }
+        Err(e) => {
+            eprintln!("[ERROR]: Can't save {}: {e}", path.display());
+            EXIT_FAILURE
+        }
+    }
 }
@@ -0,0 +1,76 @@
+use crate::commands::rename;
+use crate::utils::constants::*;
+use deskforge::desktop::id::{self, user_path};
+use deskforge::launchers::{self, locate};
+
+use std::fs::remove_file;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+
+/// Copies the system launcher `id` byte for byte into the user's
+/// applications directory, where it takes precedence, never replacing a
+/// file. Returns the copy. Prints nothing, so the browser uses it too.
+pub fn copy_system(id: &str) -> io::Result<PathBuf> {
+    let source = locate(id, &launchers::system_dirs()).ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::NotFound,
+            format!("No system launcher with ID {id}"),
+        )
+    })?;
+    rename::copy_to(&source, id, None)
+}
+
+/// [`copy_system`] for the command line, keeping an existing copy.
+pub fn copy_to_user(id: &str) -> Result<PathBuf, i32> {
+    match copy_system(id) {
+        Ok(target) => {
+            println!("[DESKFORGE]: Copied {id} to {}", target.display());
+            Ok(target)
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(user_path(id)),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            eprintln!("[ERROR]: {e}");
+            Err(EXIT_FAILURE)
+        }
+        Err(e) => {
+            eprintln!("[ERROR]: Can't copy {id}: {e}");
+            Err(EXIT_FAILURE)
+        }
+    }
+}
+
+/// Overrides the system launcher `id`, keeping an existing override.
+/// Returns the file name to open in the editor.
+pub fn override_launcher(id: &str) -> Result<String, i32> {
+    let id = id::normalize(id);
+
+    if user_path(&id).exists() {
+        println!("[DESKFORGE]: {id} is already overridden");
+        return Ok(id);
+    }
+
+    copy_to_user(&id).map(|_| id)
+}
+
+/// Deletes the user's override of `id` so the system launcher shows again.
+/// Launchers that only exist in the user's directory are left alone.
+pub fn reset(id: &str) -> i32 {
+    let id = id::normalize(id);
+    let target = user_path(&id);
+
+    if !target.exists() {
+        eprintln!("[ERROR]: {id} is not overridden");
+        return EXIT_FAILURE;
+    }
+    let Some(source) = locate(&id, &launchers::system_dirs()) else {
+        eprintln!("[ERROR]: {id} is not an override of a system launcher, use --remove");
+        return EXIT_FAILURE;
+    };
+
+    if let Err(e) = remove_file(&target) {
+        eprintln!("[ERROR]: Can't remove {}: {e}", target.display());
+        return EXIT_FAILURE;
+    }
+    println!("[DESKFORGE]: Restored {}", source.display());
+    0
+}
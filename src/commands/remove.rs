@@ -1,22 +1,177 @@
-use std::fs::remove_file;
+use crate::utils::constants::*;
+use deskforge::desktop::id;
+use deskforge::trash::{Trash, TrashedFile};
+use deskforge::xdg;
 
-pub fn remove_err(file_name: &str) -> bool {
-    let path = dirs::data_dir()
-        .unwrap()
-        .join("applications")
-        .join(file_name);
+use std::fs::read_dir;
+use std::io::{Write, stdin, stdout};
+use std::path::PathBuf;
 
-    if !path.exists() {
-        return true;
+/// A launcher name as a file name. A glob ending in `*` already matches
+/// the extension.
+fn file_pattern(name: &str) -> String {
+    match name.trim() {
+        glob if glob.ends_with('*') => glob.to_string(),
+        name => id::normalize(name),
     }
+}
+
+/// Shell-style matching of `*` and `?`.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
 
-    return false;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((sp, sn)) => {
+                    p = sp + 1;
+                    n = sn + 1;
+                    star = Some((sp, sn + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
 }
-pub fn remove(file_name: &str) {
-    let path = dirs::data_dir()
-        .unwrap()
-        .join("applications")
-        .join(file_name);
 
-    remove_file(&path).unwrap();
+/// Launchers named by `names`, which may be globs like `steam-*`.
+fn resolve(names: &[String]) -> Result<Vec<PathBuf>, String> {
+    let dir = xdg::applications_home();
+    let mut files: Vec<String> = read_dir(&dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().is_file())
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .filter(|n| n.ends_with(".desktop"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+
+    let mut paths = Vec::new();
+    for name in names {
+        let pattern = file_pattern(name);
+        let matched: Vec<&String> = if pattern.contains(['*', '?']) {
+            files.iter().filter(|f| glob_match(&pattern, f)).collect()
+        } else {
+            files.iter().filter(|f| **f == pattern).collect()
+        };
+        if matched.is_empty() && pattern.contains(['*', '?']) {
+            return Err(format!("{pattern} doesn't match any launcher"));
+        }
+        if matched.is_empty() {
+            return Err(format!("{pattern} doesn't exist"));
+        }
+        for file in matched {
+            let path = dir.join(file);
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = stdout().flush();
+    let mut answer = String::new();
+    stdin().read_line(&mut answer).is_ok() && matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+/// Moves launchers to the trash. Several launchers, or a glob, are confirmed
+/// first unless `yes` is set. Returns the exit code.
+pub fn remove(names: &[String], yes: bool) -> i32 {
+    let paths = match resolve(names) {
+        Ok(paths) => paths,
+        Err(e) => {
+            eprintln!("[ERROR]: {e}");
+            return EXIT_FAILURE;
+        }
+    };
+
+    let globbed = names.iter().any(|n| n.contains(['*', '?']));
+    if !yes && (paths.len() > 1 || globbed) {
+        for path in &paths {
+            println!("  {}", path.display());
+        }
+        if !confirm(&format!("Move {} launcher(s) to the trash?", paths.len())) {
+            println!("[DESKFORGE]: Nothing removed");
+            return EXIT_FAILURE;
+        }
+    }
+
+    let trash = Trash::home();
+    let mut code = 0;
+    for path in paths {
+        match trash.put(&path) {
+            Ok(_) => println!("[DESKFORGE]: Moved {} to the trash", path.display()),
+            Err(e) => {
+                eprintln!("[ERROR]: Can't remove {}: {e}", path.display());
+                code = EXIT_FAILURE;
+            }
+        }
+    }
+    code
+}
+
+/// Launchers from the applications directory that are in the trash.
+fn trashed_launchers(trash: &Trash) -> Vec<TrashedFile> {
+    let dir = xdg::applications_home();
+    trash
+        .list()
+        .into_iter()
+        .filter(|item| item.original.parent() == Some(dir.as_path()))
+        .collect()
+}
+
+/// Brings back the most recently removed launcher called `name`, or lists
+/// the removed launchers when no name is given.
+pub fn restore(name: Option<&str>) -> i32 {
+    let trash = Trash::home();
+    let items = trashed_launchers(&trash);
+
+    let Some(name) = name else {
+        println!("[DESKFORGE]");
+        for item in &items {
+            let file_name = item.original.file_name().unwrap_or_default();
+            println!("{}  {}", item.deleted, file_name.to_string_lossy());
+        }
+        println!("Total: {}", items.len());
+        return 0;
+    };
+
+    let file_name = id::normalize(name);
+    let Some(item) = items
+        .iter()
+        .rev()
+        .find(|item| item.original.file_name().is_some_and(|n| *n == *file_name))
+    else {
+        eprintln!("[ERROR]: {file_name} is not in the trash");
+        return EXIT_FAILURE;
+    };
+
+    match trash.restore(item) {
+        Ok(()) => {
+            println!("[DESKFORGE]: Restored {}", item.original.display());
+            0
+        }
+        Err(e) => {
+            eprintln!("[ERROR]: Can't restore {file_name}: {e}");
+            EXIT_FAILURE
+        }
+    }
 }
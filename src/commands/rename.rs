@@ -0,0 +1,163 @@
+use crate::cli::{CopyArgs, MoveArgs};
+use crate::utils::constants::*;
+use deskforge::atomic;
+use deskforge::desktop::id::{self, user_path};
+use deskforge::desktop::lint::Severity;
+use deskforge::desktop::{DesktopEntry, Value};
+use deskforge::launchers::{self, locate};
+use deskforge::references;
+use deskforge::xdg;
+
+use std::fs::{create_dir_all, read_to_string};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// Normalizes a new desktop file ID and refuses one that can't be a file
+/// name in the applications directory.
+pub fn check_id(id: &str) -> Result<String, String> {
+    let normalized = id::normalize(id);
+    match id::check(&normalized)
+        .into_iter()
+        .find(|d| d.severity == Severity::Error)
+    {
+        Some(error) => Err(format!("{normalized}: {}", error.message)),
+        None => Ok(normalized),
+    }
+}
+
+/// Prints what keeps `id` from being a valid D-Bus name.
+fn warn_id(id: &str) {
+    for diagnostic in id::check(id)
+        .iter()
+        .filter(|d| d.severity == Severity::Warning)
+    {
+        eprintln!("[WARNING]: {id}: {}", diagnostic.message);
+    }
+}
+
+/// Copies the launcher at `source` to `id` in the user's applications
+/// directory, never replacing a file. With `name`, `Name=` is changed too.
+pub fn copy_to(source: &Path, id: &str, name: Option<&str>) -> io::Result<PathBuf> {
+    let mut content = read_to_string(source)?;
+    if let Some(name) = name {
+        let mut entry = DesktopEntry::parse(&content)
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e.to_string()))?;
+        entry.set("Name", Value::String(name.to_string()));
+        content = entry.serialize();
+    }
+
+    let target = user_path(id);
+    if let Some(parent) = target.parent() {
+        create_dir_all(parent)?;
+    }
+    atomic::create(&target, &content)?;
+    Ok(target)
+}
+
+/// Renames the user's launcher at `source` to `id`. `Name=` is unchanged.
+pub fn move_to(source: &Path, id: &str) -> io::Result<PathBuf> {
+    let target = user_path(id);
+    atomic::rename(source, &target)?;
+    Ok(target)
+}
+
+/// Warnings about what still uses `old_id` after it was renamed away.
+pub fn stale_references(old_id: &str) -> Vec<String> {
+    let mut warnings: Vec<String> = references::find(old_id)
+        .into_iter()
+        .map(|r| match r.line {
+            Some(line) => format!("{}:{line} still refers to {old_id}", r.path.display()),
+            None => format!("{} still starts {old_id}", r.path.display()),
+        })
+        .collect();
+    if let Some(system) = locate(old_id, &launchers::system_dirs()) {
+        warnings.push(format!(
+            "{old_id} overrode {}, which shows again",
+            system.display()
+        ));
+    }
+    warnings
+}
+
+/// `deskforge cp`: copies any launcher, user or system, to a new ID.
+pub fn cp(args: CopyArgs) -> i32 {
+    let source = if args.source.contains('/') {
+        Some(PathBuf::from(&args.source))
+    } else {
+        locate(&id::normalize(&args.source), &xdg::application_dirs())
+    };
+    let Some(source) = source.filter(|p| p.is_file()) else {
+        eprintln!("[ERROR]: No launcher {}", args.source);
+        return EXIT_FAILURE;
+    };
+    let id = match check_id(&args.dest) {
+        Ok(id) => id,
+        Err(e) => {
+            eprintln!("[ERROR]: {e}");
+            return EXIT_INVALID;
+        }
+    };
+
+    match copy_to(&source, &id, args.name.as_deref()) {
+        Ok(target) => {
+            println!(
+                "[DESKFORGE]: Copied {} to {}",
+                source.display(),
+                target.display()
+            );
+            warn_id(&id);
+            0
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            eprintln!("[ERROR]: {id} already exists");
+            EXIT_EXISTS
+        }
+        Err(e) => {
+            eprintln!("[ERROR]: Can't copy {}: {e}", source.display());
+            EXIT_FAILURE
+        }
+    }
+}
+
+/// `deskforge mv`: renames one of the user's launchers.
+pub fn mv(args: MoveArgs) -> i32 {
+    let old_id = id::normalize(&args.source);
+    let source = user_path(&old_id);
+    if !source.is_file() {
+        match locate(&old_id, &launchers::system_dirs()) {
+            Some(_) => eprintln!("[ERROR]: {old_id} is a system launcher, use cp"),
+            None => eprintln!("[ERROR]: No launcher {old_id}"),
+        }
+        return EXIT_FAILURE;
+    }
+    let id = match check_id(&args.dest) {
+        Ok(id) => id,
+        Err(e) => {
+            eprintln!("[ERROR]: {e}");
+            return EXIT_INVALID;
+        }
+    };
+
+    match move_to(&source, &id) {
+        Ok(target) => {
+            println!(
+                "[DESKFORGE]: Renamed {} to {}",
+                source.display(),
+                target.display()
+            );
+            warn_id(&id);
+            for warning in stale_references(&old_id) {
+                eprintln!("[WARNING]: {warning}");
+            }
+            0
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            eprintln!("[ERROR]: {id} already exists");
+            EXIT_EXISTS
+        }
+        Err(e) => {
+            eprintln!("[ERROR]: Can't rename {}: {e}", source.display());
+            EXIT_FAILURE
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::desktop::entry::DesktopEntry;
+use crate::desktop::parser::is_valid_key;
+use crate::desktop::value::Value;
+
+pub const ACTION_GROUP_PREFIX: &str = "Desktop Action ";
+
+/// One `[Desktop Action <id>]` group referenced from `Actions=`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Action {
+    pub id: String,
+    pub name: String,
+    pub exec: String,
+    pub icon: String,
+}
+
+pub fn group_name(id: &str) -> String {
+    format!("{ACTION_GROUP_PREFIX}{id}")
+}
+
+/// Action identifiers follow the same rules as keys.
+pub fn is_valid_id(id: &str) -> bool {
+    is_valid_key(id)
+}
+
+/// Reads the actions listed in `Actions=`, in order, together with their
+/// groups. Listed actions without a group come back with empty fields.
+pub fn read(entry: &DesktopEntry) -> Vec<Action> {
+    let ids = entry.get_list("Actions").unwrap_or_default();
+
+    ids.iter()
+        .map(|id| {
+            let group = entry.group(&group_name(id));
+            let field = |key: &str| {
+                group
+                    .and_then(|g| g.get(key))
+                    .map(|v| v.as_str().map_or_else(|| v.to_raw(), str::to_string))
+                    .unwrap_or_default()
+            };
+            Action {
+                id: id.clone(),
+                name: field("Name"),
+                exec: field("Exec"),
+                icon: field("Icon"),
+            }
+        })
+        .collect()
+}
+
+/// Writes `Actions=` and one group per action. Groups of actions that are no
+/// longer listed are removed; everything else in existing groups is kept.
+/// Actions without an id are skipped.
+pub fn write(entry: &mut DesktopEntry, actions: &[Action]) {
+    let actions: Vec<&Action> = actions.iter().filter(|a| !a.id.is_empty()).collect();
+    let ids: Vec<String> = actions.iter().map(|a| a.id.clone()).collect();
+
+    if ids.is_empty() {
+        if entry.get("Actions").is_some() {
+            entry.remove("Actions");
+        }
+    } else {
+        entry.set("Actions", Value::List(ids.clone()));
+    }
+
+    let stale: Vec<String> = entry
+        .groups
+        .iter()
+        .filter(|g| {
+            g.name
+                .strip_prefix(ACTION_GROUP_PREFIX)
+                .is_some_and(|id| !ids.iter().any(|i| i == id))
+        })
+        .map(|g| g.name.clone())
+        .collect();
+    for name in stale {
+        entry.remove_group(&name);
+    }
+
+    for action in actions {
+        let group = entry.group_or_insert(&group_name(&action.id));
+        for (key, value) in [
+            ("Name", &action.name),
+            ("Exec", &action.exec),
+            ("Icon", &action.icon),
+        ] {
+            if value.is_empty() {
+                group.remove(key);
+            } else {
+                group.set(key, Value::String(value.clone()));
+            }
+        }
+    }
+}
+
+/// Problems that would make the actions invalid once written.
+pub fn check(actions: &[Action]) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    for (idx, action) in actions.iter().enumerate() {
+        let label = if action.id.is_empty() {
+            format!("Action #{}", idx + 1)
+        } else {
+            action.id.clone()
+        };
+
+        if action.id.is_empty() {
+            warnings.push(format!("{label} has no id and will not be saved"));
+        } else if !is_valid_id(&action.id) {
+            warnings.push(format!("{label}: id may only contain A-Z, a-z, 0-9 and -"));
+        } else if actions[..idx].iter().any(|a| a.id == action.id) {
+            warnings.push(format!("{label}: duplicate id"));
+        }
+
+        if action.name.trim().is_empty() {
+            warnings.push(format!("{label}: Name is required"));
+        }
+    }
+
+    warnings
+}
@@ -0,0 +1,267 @@
+use crate::desktop::entry::DesktopEntry;
+use crate::desktop::value::{Value, split_list};
+
+/// Main categories from the Desktop Menu Specification registry.
+pub const MAIN_CATEGORIES: &[&str] = &[
+    "AudioVideo",
+    "Audio",
+    "Video",
+    "Development",
+    "Education",
+    "Game",
+    "Graphics",
+    "Network",
+    "Office",
+    "Science",
+    "Settings",
+    "System",
+    "Utility",
+];
+
+/// Additional categories and the categories they should be used with.
+/// Each inner slice is one accepted combination; an empty list means the
+/// category can be used on its own.
+pub const ADDITIONAL_CATEGORIES: &[(&str, &[&[&str]])] = &[
+    ("Building", &[&["Development"]]),
+    ("Debugger", &[&["Development"]]),
+    ("IDE", &[&["Development"]]),
+    ("GUIDesigner", &[&["Development"]]),
+    ("Profiling", &[&["Development"]]),
+    ("RevisionControl", &[&["Development"]]),
+    ("Translation", &[&["Development"]]),
+    ("Calendar", &[&["Office"]]),
+    ("ContactManagement", &[&["Office"]]),
+    (
+        "Database",
+        &[&["Office"], &["Development"], &["AudioVideo"]],
+    ),
+    ("Dictionary", &[&["Office"], &["TextTools"]]),
+    ("Chart", &[&["Office"]]),
+    ("Email", &[&["Office"], &["Network"]]),
+    ("Finance", &[&["Office"]]),
+    ("FlowChart", &[&["Office"]]),
+    ("PDA", &[&["Office"]]),
+    ("ProjectManagement", &[&["Office"], &["Development"]]),
+    ("Presentation", &[&["Office"]]),
+    ("Spreadsheet", &[&["Office"]]),
+    ("WordProcessor", &[&["Office"]]),
+    ("2DGraphics", &[&["Graphics"]]),
+    ("VectorGraphics", &[&["Graphics", "2DGraphics"]]),
+    ("RasterGraphics", &[&["Graphics", "2DGraphics"]]),
+    ("3DGraphics", &[&["Graphics"]]),
+    ("Scanning", &[&["Graphics"]]),
+    ("OCR", &[&["Graphics", "Scanning"]]),
+    ("Photography", &[&["Graphics"], &["Office"]]),
+    ("Publishing", &[&["Graphics"], &["Office"]]),
+    ("Viewer", &[&["Graphics"], &["Office"]]),
+    ("TextTools", &[&["Utility"]]),
+    ("DesktopSettings", &[&["Settings"]]),
+    ("HardwareSettings", &[&["Settings"]]),
+    ("Printing", &[&["HardwareSettings", "Settings"]]),
+    ("PackageManager", &[&["Settings"]]),
+    ("Dialup", &[&["Network"]]),
+    ("InstantMessaging", &[&["Network"]]),
+    ("Chat", &[&["Network"]]),
+    ("IRCClient", &[&["Network"]]),
+    ("Feed", &[&["Network"]]),
+    ("FileTransfer", &[&["Network"]]),
+    ("HamRadio", &[&["Network"], &["Audio"]]),
+    ("News", &[&["Network"]]),
+    ("P2P", &[&["Network"]]),
+    ("RemoteAccess", &[&["Network"]]),
+    ("Telephony", &[&["Network"]]),
+    ("TelephonyTools", &[&["Utility"]]),
+    ("VideoConference", &[&["Network"]]),
+    ("WebBrowser", &[&["Network"]]),
+    ("WebDevelopment", &[&["Network"], &["Development"]]),
+    ("Midi", &[&["AudioVideo", "Audio"]]),
+    ("Mixer", &[&["AudioVideo", "Audio"]]),
+    ("Sequencer", &[&["AudioVideo", "Audio"]]),
+    ("Tuner", &[&["AudioVideo", "Audio"]]),
+    ("TV", &[&["AudioVideo", "Video"]]),
+    (
+        "AudioVideoEditing",
+        &[&["Audio"], &["Video"], &["AudioVideo"]],
+    ),
+    ("Player", &[&["Audio"], &["Video"], &["AudioVideo"]]),
+    ("Recorder", &[&["Audio"], &["Video"], &["AudioVideo"]]),
+    ("DiscBurning", &[&["AudioVideo"]]),
+    ("ActionGame", &[&["Game"]]),
+    ("AdventureGame", &[&["Game"]]),
+    ("ArcadeGame", &[&["Game"]]),
+    ("BoardGame", &[&["Game"]]),
+    ("BlocksGame", &[&["Game"]]),
+    ("CardGame", &[&["Game"]]),
+    ("KidsGame", &[&["Game"]]),
+    ("LogicGame", &[&["Game"]]),
+    ("RolePlaying", &[&["Game"]]),
+    ("Shooter", &[&["Game"]]),
+    ("Simulation", &[&["Game"]]),
+    ("SportsGame", &[&["Game"]]),
+    ("StrategyGame", &[&["Game"]]),
+    ("Art", &[&["Education"], &["Science"]]),
+    ("Construction", &[&["Education"], &["Science"]]),
+    ("Music", &[&["AudioVideo"], &["Education"]]),
+    ("Languages", &[&["Education"], &["Science"]]),
+    ("ArtificialIntelligence", &[&["Education"], &["Science"]]),
+    ("Astronomy", &[&["Education"], &["Science"]]),
+    ("Biology", &[&["Education"], &["Science"]]),
+    ("Chemistry", &[&["Education"], &["Science"]]),
+    ("ComputerScience", &[&["Education"], &["Science"]]),
+    ("DataVisualization", &[&["Education"], &["Science"]]),
+    ("Economy", &[&["Education"], &["Science"]]),
+    ("Electricity", &[&["Education"], &["Science"]]),
+    ("Geography", &[&["Education"], &["Science"]]),
+    ("Geology", &[&["Education"], &["Science"]]),
+    ("Geoscience", &[&["Education"], &["Science"]]),
+    ("History", &[&["Education"], &["Science"]]),
+    ("Humanities", &[&["Education"], &["Science"]]),
+    ("ImageProcessing", &[&["Education"], &["Science"]]),
+    ("Literature", &[&["Education"], &["Science"]]),
+    ("Maps", &[&["Education"], &["Science"], &["Utility"]]),
+    ("Math", &[&["Education"], &["Science"]]),
+    (
+        "NumericalAnalysis",
+        &[&["Education", "Math"], &["Science", "Math"]],
+    ),
+    ("MedicalSoftware", &[&["Education"], &["Science"]]),
+    ("Physics", &[&["Education"], &["Science"]]),
+    ("Robotics", &[&["Education"], &["Science"]]),
+    (
+        "Spirituality",
+        &[&["Education"], &["Science"], &["Utility"]],
+    ),
+    ("Sports", &[&["Education"], &["Science"]]),
+    (
+        "ParallelComputing",
+        &[
+            &["Education", "ComputerScience"],
+            &["Science", "ComputerScience"],
+        ],
+    ),
+    ("Amusement", &[]),
+    ("Archiving", &[&["Utility"]]),
+    ("Compression", &[&["Utility"]]),
+    ("Electronics", &[]),
+    ("Emulator", &[&["System"], &["Game"]]),
+    ("Engineering", &[]),
+    ("FileTools", &[&["Utility"], &["System"]]),
+    ("FileManager", &[&["System", "FileTools"]]),
+    ("TerminalEmulator", &[&["System"]]),
+    ("Filesystem", &[&["System"]]),
+    ("Monitor", &[&["System"]]),
+    ("Security", &[&["Settings"], &["System"]]),
+    ("Accessibility", &[&["Settings"], &["Utility"]]),
+    ("Calculator", &[&["Utility"]]),
+    ("Clock", &[&["Utility"]]),
+    ("TextEditor", &[&["Utility"]]),
+    ("Documentation", &[]),
+    ("Adult", &[]),
+    ("Core", &[]),
+    ("KDE", &[&["Qt"]]),
+    ("GNOME", &[&["GTK"]]),
+    ("XFCE", &[&["GTK"]]),
+    ("DDE", &[&["Qt"]]),
+    ("GTK", &[]),
+    ("Qt", &[]),
+    ("Motif", &[]),
+    ("Java", &[]),
+    ("ConsoleOnly", &[]),
+];
+
+/// Reserved categories that need an `OnlyShowIn` key.
+pub const RESERVED_CATEGORIES: &[&str] = &["Screensaver", "TrayIcon", "Applet", "Shell"];
+
+pub fn is_main(category: &str) -> bool {
+    MAIN_CATEGORIES.contains(&category)
+}
+
+pub fn is_additional(category: &str) -> bool {
+    ADDITIONAL_CATEGORIES
+        .iter()
+        .any(|(name, _)| *name == category)
+}
+
+pub fn is_reserved(category: &str) -> bool {
+    RESERVED_CATEGORIES.contains(&category)
+}
+
+/// Registered categories plus anything using the `X-` extension prefix.
+pub fn is_known(category: &str) -> bool {
+    is_main(category)
+        || is_additional(category)
+        || is_reserved(category)
+        || category.starts_with("X-")
+}
+
+/// The accepted combinations of categories `category` should appear with.
+pub fn related(category: &str) -> &'static [&'static [&'static str]] {
+    ADDITIONAL_CATEGORIES
+        .iter()
+        .find(|(name, _)| *name == category)
+        .map_or(&[], |(_, related)| related)
+}
+
+/// Every registered category, main categories first.
+pub fn all() -> impl Iterator<Item = &'static str> {
+    MAIN_CATEGORIES
+        .iter()
+        .copied()
+        .chain(ADDITIONAL_CATEGORIES.iter().map(|(name, _)| *name))
+}
+
+/// Checks a `Categories=` list against the rules of the menu specification
+/// and returns a message for every problem found.
+pub fn check(categories: &[String]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let has = |c: &str| categories.iter().any(|x| x == c);
+
+    if !categories.is_empty() && !categories.iter().any(|c| is_main(c)) {
+        warnings.push("No main category, the menu may not show this launcher".to_string());
+    }
+
+    for category in categories {
+        if !is_known(category) {
+            warnings.push(format!("{category} is not a registered category"));
+            continue;
+        }
+
+        if (category == "Audio" || category == "Video") && !has("AudioVideo") {
+            warnings.push(format!("{category} should be used with AudioVideo"));
+        }
+
+        let related = related(category);
+        if !related.is_empty() && !related.iter().any(|combo| combo.iter().all(|c| has(c))) {
+            let options: Vec<String> = related.iter().map(|combo| combo.join("+")).collect();
+            warnings.push(format!("{category} requires {}", options.join(" or ")));
+        }
+    }
+
+    warnings
+}
+
+/// Moves a legacy `Category=` value into `Categories=`. Returns whether the
+/// entry was changed.
+pub fn migrate_legacy(entry: &mut DesktopEntry) -> bool {
+    let Some(legacy) = entry.get("Category").cloned() else {
+        return false;
+    };
+
+    let mut categories: Vec<String> = entry.get_list("Categories").unwrap_or_default().to_vec();
+    let legacy = match legacy {
+        Value::List(items) => items,
+        other => split_list(&other.to_raw()),
+    };
+    for category in legacy {
+        let category = category.trim().to_string();
+        if !category.is_empty() && !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+
+    entry.remove("Category");
+    if !categories.is_empty() {
+        entry.set("Categories", Value::List(categories));
+    }
+    true
+}
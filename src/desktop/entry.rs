@@ -0,0 +1,290 @@
+use crate::desktop::MAIN_GROUP;
+use crate::desktop::locale;
+use crate::desktop::parser::{self, ParseError};
+use crate::desktop::value::Value;
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub key: String,
+    pub locale: Option<String>,
+    pub value: Value,
+    /// The line as it was read from disk. Dropped as soon as the value
+    /// changes so untouched entries are written back byte for byte.
+    pub raw: Option<String>,
+}
+
+/// A line inside a group: either a `key=value` pair or something the spec
+/// tells us to ignore (comments, blank lines) that is kept verbatim.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Line {
+    Entry(Entry),
+    Raw(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    pub name: String,
+    pub header: Option<String>,
+    pub lines: Vec<Line>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DesktopEntry {
+    /// Comments and blank lines before the first group header.
+    pub preamble: Vec<String>,
+    pub groups: Vec<Group>,
+    pub trailing_newline: bool,
+}
+
+impl Entry {
+    pub fn new(key: &str, locale: Option<&str>, value: Value) -> Self {
+        Self {
+            key: key.to_string(),
+            locale: locale.map(str::to_string),
+            value,
+            raw: None,
+        }
+    }
+
+    pub fn full_key(&self) -> String {
+        match &self.locale {
+            Some(locale) => format!("{}[{}]", self.key, locale),
+            None => self.key.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.raw {
+            Some(raw) => f.write_str(raw),
+            None => write!(f, "{}={}", self.full_key(), self.value),
+        }
+    }
+}
+
+impl Group {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            header: None,
+            lines: Vec::new(),
+        }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.lines.iter().filter_map(|line| match line {
+            Line::Entry(entry) => Some(entry),
+            Line::Raw(_) => None,
+        })
+    }
+
+    fn entries_mut(&mut self) -> impl Iterator<Item = &mut Entry> {
+        self.lines.iter_mut().filter_map(|line| match line {
+            Line::Entry(entry) => Some(entry),
+            Line::Raw(_) => None,
+        })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.get_localized(key, None)
+    }
+
+    pub fn get_localized(&self, key: &str, locale: Option<&str>) -> Option<&Value> {
+        self.entries()
+            .find(|e| e.key == key && e.locale.as_deref() == locale)
+            .map(|e| &e.value)
+    }
+
+    /// The value of `key` a user with `locale` sees: the best matching
+    /// translation, or the unlocalized value.
+    pub fn get_for_locale(&self, key: &str, locale: Option<&str>) -> Option<&Value> {
+        locale
+            .map(locale::candidates)
+            .unwrap_or_default()
+            .iter()
+            .find_map(|candidate| self.get_localized(key, Some(candidate)))
+            .or_else(|| self.get(key))
+    }
+
+    /// Every translation of `key` as `(locale, value)`, in file order.
+    pub fn translations(&self, key: &str) -> Vec<(&str, &Value)> {
+        self.entries()
+            .filter(|e| e.key == key)
+            .filter_map(|e| Some((e.locale.as_deref()?, &e.value)))
+            .collect()
+    }
+
+    /// Replaces the value of `key` in place, or adds it after the last entry
+    /// of the group, or of the key for a translation. Setting a key to the
+    /// value it already has is a no-op.
+    pub fn set(&mut self, key: &str, value: Value) {
+        self.set_localized(key, None, value);
+    }
+
+    pub fn set_localized(&mut self, key: &str, locale: Option<&str>, value: Value) {
+        if let Some(entry) = self
+            .entries_mut()
+            .find(|e| e.key == key && e.locale.as_deref() == locale)
+        {
+            if entry.value != value {
+                entry.value = value;
+                entry.raw = None;
+            }
+            return;
+        }
+
+        // Translations go next to the other lines of their key.
+        let at = self
+            .lines
+            .iter()
+            .rposition(|line| matches!(line, Line::Entry(e) if locale.is_some() && e.key == key))
+            .or_else(|| {
+                self.lines
+                    .iter()
+                    .rposition(|line| matches!(line, Line::Entry(_)))
+            })
+            .map_or(0, |idx| idx + 1);
+        self.lines
+            .insert(at, Line::Entry(Entry::new(key, locale, value)));
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.remove_localized(key, None);
+    }
+
+    pub fn remove_localized(&mut self, key: &str, locale: Option<&str>) {
+        self.lines.retain(|line| match line {
+            Line::Entry(e) => !(e.key == key && e.locale.as_deref() == locale),
+            Line::Raw(_) => true,
+        });
+    }
+}
+
+impl DesktopEntry {
+    pub fn new() -> Self {
+        Self {
+            preamble: Vec::new(),
+            groups: vec![Group::new(MAIN_GROUP)],
+            trailing_newline: true,
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        parser::parse(input)
+    }
+
+    pub fn group(&self, name: &str) -> Option<&Group> {
+        self.groups.iter().find(|g| g.name == name)
+    }
+
+    pub fn group_mut(&mut self, name: &str) -> Option<&mut Group> {
+        self.groups.iter_mut().find(|g| g.name == name)
+    }
+
+    /// Returns the group called `name`, creating it at the end if needed.
+    pub fn group_or_insert(&mut self, name: &str) -> &mut Group {
+        let idx = match self.groups.iter().position(|g| g.name == name) {
+            Some(idx) => idx,
+            None => {
+                if let Some(last) = self.groups.last_mut()
+                    && !matches!(last.lines.last(), Some(Line::Raw(raw)) if raw.trim().is_empty())
+                {
+                    last.lines.push(Line::Raw(String::new()));
+                }
+                self.groups.push(Group::new(name));
+                self.groups.len() - 1
+            }
+        };
+        &mut self.groups[idx]
+    }
+
+    /// Removes a group along with the blank line left in front of it when it
+    /// was the last one.
+    pub fn remove_group(&mut self, name: &str) {
+        let was_last = self.groups.last().is_some_and(|g| g.name == name);
+        self.groups.retain(|g| g.name != name);
+        if was_last && let Some(last) = self.groups.last_mut() {
+            while matches!(last.lines.last(), Some(Line::Raw(raw)) if raw.trim().is_empty()) {
+                last.lines.pop();
+            }
+        }
+    }
+
+    pub fn main(&self) -> Option<&Group> {
+        self.group(MAIN_GROUP)
+    }
+
+    pub fn main_mut(&mut self) -> &mut Group {
+        self.group_or_insert(MAIN_GROUP)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.main().and_then(|g| g.get(key))
+    }
+
+    pub fn get_for_locale(&self, key: &str, locale: Option<&str>) -> Option<&Value> {
+        self.main()?.get_for_locale(key, locale)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Value::as_str)
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(Value::as_bool)
+    }
+
+    pub fn get_list(&self, key: &str) -> Option<&[String]> {
+        self.get(key).and_then(Value::as_list)
+    }
+
+    pub fn set(&mut self, key: &str, value: Value) {
+        self.main_mut().set(key, value);
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.main_mut().remove(key);
+    }
+
+    pub fn serialize(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for DesktopEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines: Vec<String> = self.preamble.clone();
+
+        for group in &self.groups {
+            lines.push(
+                group
+                    .header
+                    .clone()
+                    .unwrap_or_else(|| format!("[{}]", group.name)),
+            );
+            for line in &group.lines {
+                match line {
+                    Line::Entry(entry) => lines.push(entry.to_string()),
+                    Line::Raw(raw) => lines.push(raw.clone()),
+                }
+            }
+        }
+
+        f.write_str(&lines.join("\n"))?;
+        if self.trailing_newline && !lines.is_empty() {
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits `Name[de_DE]` into `("Name", Some("de_DE"))`.
+pub fn split_locale(key: &str) -> (&str, Option<&str>) {
+    match key.strip_suffix(']').and_then(|k| k.split_once('[')) {
+        Some((key, locale)) => (key, Some(locale)),
+        None => (key, None),
+    }
+}
@@ -0,0 +1,307 @@
+use is_executable::IsExecutable;
+
+use std::env;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Characters that must be inside a quoted argument.
+pub const RESERVED: &[char] = &[
+    '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(', ')', '`',
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldCode {
+    /// `%f`
+    File,
+    /// `%F`
+    Files,
+    /// `%u`
+    Url,
+    /// `%U`
+    Urls,
+    /// `%i`
+    Icon,
+    /// `%c`
+    Name,
+    /// `%k`
+    Location,
+}
+
+impl FieldCode {
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'f' => Some(FieldCode::File),
+            'F' => Some(FieldCode::Files),
+            'u' => Some(FieldCode::Url),
+            'U' => Some(FieldCode::Urls),
+            'i' => Some(FieldCode::Icon),
+            'c' => Some(FieldCode::Name),
+            'k' => Some(FieldCode::Location),
+            _ => None,
+        }
+    }
+
+    pub fn as_char(self) -> char {
+        match self {
+            FieldCode::File => 'f',
+            FieldCode::Files => 'F',
+            FieldCode::Url => 'u',
+            FieldCode::Urls => 'U',
+            FieldCode::Icon => 'i',
+            FieldCode::Name => 'c',
+            FieldCode::Location => 'k',
+        }
+    }
+
+    /// `%f`, `%F`, `%u` and `%U` all receive the files or URLs to open.
+    pub fn is_target(self) -> bool {
+        matches!(
+            self,
+            FieldCode::File | FieldCode::Files | FieldCode::Url | FieldCode::Urls
+        )
+    }
+
+    /// Codes expanding to several arguments must be arguments on their own.
+    pub fn is_list(self) -> bool {
+        matches!(self, FieldCode::Files | FieldCode::Urls)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arg {
+    /// The argument with quotes and escapes removed. `%%` stays as is.
+    pub text: String,
+    /// Character offset of the argument in the Exec value.
+    pub start: usize,
+    pub quoted: bool,
+    pub field_codes: Vec<(FieldCode, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecErrorKind {
+    Empty,
+    UnterminatedQuote,
+    InvalidEscape(char),
+    UnquotedReserved(char),
+    InvalidFieldCode(char),
+    DeprecatedFieldCode(char),
+    FieldCodeInQuotes(char),
+    /// A second `%f`/`%F`/`%u`/`%U`, e.g. `%F` together with `%f`.
+    MultipleTargetCodes(char, char),
+    ListCodeNotAlone(char),
+    TrailingPercent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecError {
+    /// 0-based character offset in the Exec value.
+    pub pos: usize,
+    pub kind: ExecErrorKind,
+}
+
+impl fmt::Display for ExecErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecErrorKind::Empty => write!(f, "empty command"),
+            ExecErrorKind::UnterminatedQuote => write!(f, "unterminated quote"),
+            ExecErrorKind::InvalidEscape(c) => write!(f, "invalid escape \"\\{c}\" in quotes"),
+            ExecErrorKind::UnquotedReserved(c) => {
+                write!(f, "reserved character {c:?} must be quoted")
+            }
+            ExecErrorKind::InvalidFieldCode(c) => write!(f, "invalid field code %{c}"),
+            ExecErrorKind::DeprecatedFieldCode(c) => write!(f, "deprecated field code %{c}"),
+            ExecErrorKind::FieldCodeInQuotes(c) => write!(f, "field code %{c} inside quotes"),
+            ExecErrorKind::MultipleTargetCodes(a, b) => write!(f, "%{b} used together with %{a}"),
+            ExecErrorKind::ListCodeNotAlone(c) => write!(f, "%{c} must be an argument on its own"),
+            ExecErrorKind::TrailingPercent => write!(f, "lone % at end"),
+        }
+    }
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at col {}", self.kind, self.pos + 1)
+    }
+}
+
+impl Error for ExecError {}
+
+/// Splits an `Exec` value (already unescaped as a string value) into
+/// arguments following the quoting rules and field codes of the spec.
+pub fn parse(exec: &str) -> Result<Vec<Arg>, ExecError> {
+    let chars: Vec<char> = exec.chars().collect();
+    let err = |pos, kind| Err(ExecError { pos, kind });
+
+    let mut args: Vec<Arg> = Vec::new();
+    let mut target: Option<(char, usize)> = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] == ' ' {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let mut arg = Arg {
+            text: String::new(),
+            start,
+            quoted: chars[i] == '"',
+            field_codes: Vec::new(),
+        };
+
+        if arg.quoted {
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return err(start, ExecErrorKind::UnterminatedQuote),
+                    Some('"') => {
+                        i += 1;
+                        break;
+                    }
+                    Some('\\') => match chars.get(i + 1) {
+                        Some(c @ ('"' | '`' | '$' | '\\')) => {
+                            arg.text.push(*c);
+                            i += 2;
+                        }
+                        Some(c) => return err(i, ExecErrorKind::InvalidEscape(*c)),
+                        None => return err(start, ExecErrorKind::UnterminatedQuote),
+                    },
+                    Some('%') => match chars.get(i + 1) {
+                        Some('%') => {
+                            arg.text.push_str("%%");
+                            i += 2;
+                        }
+                        Some(&c) if FieldCode::from_char(c).is_some() || is_deprecated(c) => {
+                            return err(i, ExecErrorKind::FieldCodeInQuotes(c));
+                        }
+                        Some(&c) => return err(i, ExecErrorKind::InvalidFieldCode(c)),
+                        None => return err(start, ExecErrorKind::UnterminatedQuote),
+                    },
+                    Some(c) => {
+                        arg.text.push(*c);
+                        i += 1;
+                    }
+                }
+            }
+            if chars.get(i).is_some_and(|c| *c != ' ') {
+                return err(i, ExecErrorKind::UnquotedReserved(chars[i]));
+            }
+        } else {
+            while i < chars.len() && chars[i] != ' ' {
+                let c = chars[i];
+                if c == '%' {
+                    let Some(&code) = chars.get(i + 1) else {
+                        return err(i, ExecErrorKind::TrailingPercent);
+                    };
+                    if code != '%' {
+                        match FieldCode::from_char(code) {
+                            Some(field) => arg.field_codes.push((field, i)),
+                            None if is_deprecated(code) => {
+                                return err(i, ExecErrorKind::DeprecatedFieldCode(code));
+                            }
+                            None => return err(i, ExecErrorKind::InvalidFieldCode(code)),
+                        }
+                    }
+                    arg.text.push('%');
+                    arg.text.push(code);
+                    i += 2;
+                    continue;
+                }
+                if RESERVED.contains(&c) {
+                    return err(i, ExecErrorKind::UnquotedReserved(c));
+                }
+                arg.text.push(c);
+                i += 1;
+            }
+        }
+
+        for &(field, pos) in &arg.field_codes {
+            if field.is_list() && arg.text.chars().count() != 2 {
+                return err(pos, ExecErrorKind::ListCodeNotAlone(field.as_char()));
+            }
+            if field.is_target() {
+                if let Some((first, _)) = target {
+                    return err(
+                        pos,
+                        ExecErrorKind::MultipleTargetCodes(first, field.as_char()),
+                    );
+                }
+                target = Some((field.as_char(), pos));
+            }
+        }
+
+        args.push(arg);
+    }
+
+    if args.is_empty() {
+        return err(0, ExecErrorKind::Empty);
+    }
+
+    Ok(args)
+}
+
+/// `%d %D %n %N %v %m` were removed from the spec.
+fn is_deprecated(code: char) -> bool {
+    "dDnNvm".contains(code)
+}
+
+/// The program an `Exec` value starts, if it tokenizes.
+pub fn program(exec: &str) -> Option<String> {
+    parse(exec)
+        .ok()
+        .and_then(|args| args.into_iter().next())
+        .map(|arg| arg.text)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolved {
+    Found(PathBuf),
+    /// A file exists but lacks the executable bit.
+    NotExecutable(PathBuf),
+    NotFound,
+}
+
+/// Finds `program` the way a launcher would: paths are used as is, bare
+/// names are looked up in `$PATH`.
+pub fn resolve(program: &str) -> Resolved {
+    resolve_in(program, &env::var_os("PATH").unwrap_or_default())
+}
+
+pub fn resolve_in(program: &str, path_var: &OsStr) -> Resolved {
+    if program.is_empty() {
+        return Resolved::NotFound;
+    }
+
+    if program.contains('/') {
+        return check(Path::new(program));
+    }
+
+    let mut not_executable = None;
+    for dir in env::split_paths(path_var) {
+        if dir.as_os_str().is_empty() {
+            continue;
+        }
+        match check(&dir.join(program)) {
+            Resolved::Found(path) => return Resolved::Found(path),
+            Resolved::NotExecutable(path) => {
+                not_executable.get_or_insert(path);
+            }
+            Resolved::NotFound => {}
+        }
+    }
+
+    not_executable.map_or(Resolved::NotFound, Resolved::NotExecutable)
+}
+
+fn check(path: &Path) -> Resolved {
+    if !path.is_file() {
+        Resolved::NotFound
+    } else if path.is_executable() {
+        Resolved::Found(path.to_path_buf())
+    } else {
+        Resolved::NotExecutable(path.to_path_buf())
+    }
+}
@@ -0,0 +1,113 @@
+use crate::desktop::lint::{Diagnostic, SPEC_NAMING, Severity};
+use crate::xdg;
+
+use std::path::PathBuf;
+
+/// D-Bus limits well-known names to 255 bytes.
+const MAX_LENGTH: usize = 255;
+
+/// Appends `.desktop` unless `id` already ends with it.
+pub fn normalize(id: &str) -> String {
+    let id = id.trim();
+    if id.ends_with(".desktop") {
+        id.to_string()
+    } else {
+        format!("{id}.desktop")
+    }
+}
+
+/// Where the user's launcher with ID `id` is saved.
+pub fn user_path(id: &str) -> PathBuf {
+    xdg::applications_home().join(normalize(id))
+}
+
+/// A file name for a launcher called `name`: lowercase ASCII letters and
+/// digits with everything else turned into single dashes, so "My Cool
+/// App/Beta" becomes `my-cool-app-beta.desktop`. A Name that is already a
+/// reverse DNS name, like "org.example.App", is kept as it is.
+pub fn from_name(name: &str) -> String {
+    let name = name.trim().trim_end_matches(".desktop");
+    if name.contains('.') && check(name).is_empty() {
+        return normalize(name);
+    }
+
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        return String::new();
+    }
+    normalize(slug)
+}
+
+/// Checks a desktop file ID against the file naming rules: it must name a
+/// file directly in an applications directory, and should be a valid D-Bus
+/// well-known name in reverse DNS form, which `DBusActivatable` requires.
+pub fn check(id: &str) -> Vec<Diagnostic> {
+    let diagnostic = |severity, message: String| Diagnostic {
+        severity,
+        line: None,
+        message,
+        spec: SPEC_NAMING,
+    };
+
+    let name = id.trim().trim_end_matches(".desktop");
+    if name.is_empty() {
+        return vec![diagnostic(
+            Severity::Error,
+            "Desktop file ID is empty".to_string(),
+        )];
+    }
+    if name.contains('/') {
+        return vec![diagnostic(
+            Severity::Error,
+            "Desktop file IDs can't contain '/'".to_string(),
+        )];
+    }
+
+    let mut diagnostics = Vec::new();
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+    {
+        diagnostics.push(diagnostic(
+            Severity::Warning,
+            format!("'{c}' is not allowed in a D-Bus name"),
+        ));
+    }
+    let elements: Vec<&str> = name.split('.').collect();
+    if elements.iter().any(|e| e.is_empty()) {
+        diagnostics.push(diagnostic(
+            Severity::Warning,
+            "Empty element between '.' separators".to_string(),
+        ));
+    }
+    if let Some(element) = elements
+        .iter()
+        .find(|e| e.starts_with(|c: char| c.is_ascii_digit()))
+    {
+        diagnostics.push(diagnostic(
+            Severity::Warning,
+            format!("Element \"{element}\" starts with a digit"),
+        ));
+    }
+    if name.len() > MAX_LENGTH {
+        diagnostics.push(diagnostic(
+            Severity::Warning,
+            format!("Longer than {MAX_LENGTH} characters"),
+        ));
+    }
+    if elements.len() < 2 {
+        diagnostics.push(diagnostic(
+            Severity::Hint,
+            "Not a reverse DNS name like org.example.App".to_string(),
+        ));
+    }
+    diagnostics
+}
@@ -0,0 +1,592 @@
+use crate::desktop::actions::{self, ACTION_GROUP_PREFIX};
+use crate::desktop::entry::{DesktopEntry, Entry, Group, Line};
+use crate::desktop::value::{Value, ValueType};
+use crate::desktop::{MAIN_GROUP, ParseError, categories, exec};
+
+use std::fmt;
+
+/// Spec sections diagnostics point at.
+pub const SPEC_NAMING: &str = "desktop-entry-spec 2 (File naming)";
+pub const SPEC_FORMAT: &str = "desktop-entry-spec 3 (Basic format)";
+pub const SPEC_VALUES: &str = "desktop-entry-spec 4 (Value types)";
+pub const SPEC_LOCALE: &str = "desktop-entry-spec 5 (Localized values)";
+pub const SPEC_KEYS: &str = "desktop-entry-spec 6 (Recognized keys)";
+pub const SPEC_EXEC: &str = "desktop-entry-spec 7 (The Exec key)";
+pub const SPEC_ACTIONS: &str = "desktop-entry-spec 11 (Additional actions)";
+pub const SPEC_EXTENDING: &str = "desktop-entry-spec 12 (Extending the format)";
+pub const SPEC_CATEGORIES: &str = "menu-spec A (Registered categories)";
+pub const SPEC_ICONS: &str = "icon-theme-spec (Icon lookup)";
+
+/// Keys of the `[Desktop Entry]` group and the types they apply to. An empty
+/// list means every type.
+pub const KEYS: &[(&str, &[&str])] = &[
+    ("Type", &[]),
+    ("Version", &[]),
+    ("Name", &[]),
+    ("GenericName", &[]),
+    ("NoDisplay", &[]),
+    ("Comment", &[]),
+    ("Icon", &[]),
+    ("Hidden", &[]),
+    ("OnlyShowIn", &[]),
+    ("NotShowIn", &[]),
+    ("DBusActivatable", &["Application"]),
+    ("TryExec", &["Application"]),
+    ("Exec", &["Application"]),
+    ("Path", &["Application"]),
+    ("Terminal", &["Application"]),
+    ("Actions", &["Application"]),
+    ("MimeType", &["Application"]),
+    ("Categories", &["Application"]),
+    ("Implements", &[]),
+    ("Keywords", &["Application"]),
+    ("StartupNotify", &["Application"]),
+    ("StartupWMClass", &["Application"]),
+    ("URL", &["Link"]),
+    ("PrefersNonDefaultGPU", &["Application"]),
+    ("SingleMainWindow", &["Application"]),
+];
+
+/// Keys removed from the spec that tools still write.
+pub const DEPRECATED_KEYS: &[&str] = &[
+    "Encoding",
+    "MiniIcon",
+    "TerminalOptions",
+    "Protocols",
+    "Extensions",
+    "BinaryPattern",
+    "MapNotify",
+    "SwallowTitle",
+    "SwallowExec",
+    "SortOrder",
+    "FilePattern",
+    "Patterns",
+    "DefaultApp",
+    "Dev",
+    "FSType",
+    "MountPoint",
+    "ReadOnly",
+    "UnmountIcon",
+];
+
+pub const TYPES: &[&str] = &["Application", "Link", "Directory"];
+pub const VERSIONS: &[&str] = &["1.0", "1.1", "1.2", "1.3", "1.4", "1.5"];
+
+/// Desktop environments registered for `OnlyShowIn` and `NotShowIn`.
+pub const DESKTOPS: &[&str] = &[
+    "GNOME",
+    "GNOME-Classic",
+    "GNOME-Flashback",
+    "KDE",
+    "LXDE",
+    "LXQt",
+    "MATE",
+    "Razor",
+    "ROX",
+    "TDE",
+    "Unity",
+    "XFCE",
+    "EDE",
+    "Cinnamon",
+    "Pantheon",
+    "Budgie",
+    "Enlightenment",
+    "DDE",
+    "Endless",
+    "Old",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+    Hint,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// 1-based line number, if the problem belongs to a line of the file.
+    pub line: Option<usize>,
+    pub message: String,
+    pub spec: &'static str,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Hint => write!(f, "hint"),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "{line}: ")?;
+        }
+        write!(f, "{}: {} [{}]", self.severity, self.message, self.spec)
+    }
+}
+
+/// Parses `input` and checks it. A file that does not parse gives a single
+/// error for the offending line.
+pub fn lint(input: &str) -> Vec<Diagnostic> {
+    match DesktopEntry::parse(input) {
+        Ok(entry) => check(&entry),
+        Err(e) => vec![from_parse_error(&e)],
+    }
+}
+
+pub fn from_parse_error(e: &ParseError) -> Diagnostic {
+    Diagnostic {
+        severity: Severity::Error,
+        line: Some(e.line),
+        message: e.kind.to_string(),
+        spec: SPEC_FORMAT,
+    }
+}
+
+/// Whether `value` looks like `type/subtype`.
+pub fn is_mime_type(value: &str) -> bool {
+    value
+        .split_once('/')
+        .is_some_and(|(t, s)| !t.is_empty() && !s.is_empty() && !s.contains('/'))
+}
+
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(|d| d.severity == Severity::Error)
+}
+
+/// Checks a parsed entry against the Desktop Entry and Desktop Menu
+/// specifications, sorted by line.
+pub fn check(entry: &DesktopEntry) -> Vec<Diagnostic> {
+    let mut out = Lint {
+        diagnostics: Vec::new(),
+        lines: line_numbers(entry),
+    };
+
+    match entry.main() {
+        Some(main) => out.main_group(entry, main),
+        None => out.push(
+            Severity::Error,
+            None,
+            format!("missing [{MAIN_GROUP}] group"),
+            SPEC_FORMAT,
+        ),
+    }
+
+    for group in &entry.groups {
+        if group.name == MAIN_GROUP {
+            continue;
+        }
+        let line = out.group_line(&group.name);
+        if let Some(id) = group.name.strip_prefix(ACTION_GROUP_PREFIX) {
+            out.action_group(entry, group, id, line);
+        } else if !group.name.starts_with("X-") {
+            out.push(
+                Severity::Error,
+                line,
+                format!(
+                    "unknown group [{}], extensions must start with X-",
+                    group.name
+                ),
+                SPEC_EXTENDING,
+            );
+        }
+    }
+
+    out.diagnostics
+        .sort_by_key(|d| (d.line.unwrap_or(0), d.severity));
+    out.diagnostics
+}
+
+/// Line number of every group header and entry, keyed by group name and
+/// full key. Group headers use an empty key.
+fn line_numbers(entry: &DesktopEntry) -> Vec<(String, String, usize)> {
+    let mut lines = Vec::new();
+    let mut number = entry.preamble.len();
+
+    for group in &entry.groups {
+        number += 1;
+        lines.push((group.name.clone(), String::new(), number));
+        for line in &group.lines {
+            number += 1;
+            if let Line::Entry(e) = line {
+                lines.push((group.name.clone(), e.full_key(), number));
+            }
+        }
+    }
+    lines
+}
+
+struct Lint {
+    diagnostics: Vec<Diagnostic>,
+    lines: Vec<(String, String, usize)>,
+}
+
+impl Lint {
+    fn push(
+        &mut self,
+        severity: Severity,
+        line: Option<usize>,
+        message: String,
+        spec: &'static str,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            line,
+            message,
+            spec,
+        });
+    }
+
+    fn line(&self, group: &str, key: &str) -> Option<usize> {
+        self.lines
+            .iter()
+            .find(|(g, k, _)| g == group && k == key)
+            .map(|(_, _, n)| *n)
+    }
+
+    fn group_line(&self, group: &str) -> Option<usize> {
+        self.line(group, "")
+    }
+
+    fn main_group(&mut self, entry: &DesktopEntry, main: &Group) {
+        let header = self.group_line(MAIN_GROUP);
+        let line = |lint: &Self, key: &str| lint.line(MAIN_GROUP, key).or(header);
+        let kind = entry.get_str("Type").unwrap_or_default().to_string();
+
+        for key in ["Type", "Name"] {
+            if main.get(key).is_none_or(Value::is_empty) {
+                self.push(
+                    Severity::Error,
+                    header,
+                    format!("required key \"{key}\" is missing"),
+                    SPEC_KEYS,
+                );
+            }
+        }
+
+        if !kind.is_empty() && !TYPES.contains(&kind.as_str()) {
+            self.push(
+                Severity::Error,
+                line(self, "Type"),
+                format!(
+                    "unknown Type \"{kind}\", expected one of {}",
+                    TYPES.join(", ")
+                ),
+                SPEC_KEYS,
+            );
+        }
+
+        let dbus = entry.get_bool("DBusActivatable").unwrap_or(false);
+        if kind == "Application" && !dbus && main.get("Exec").is_none_or(Value::is_empty) {
+            self.push(
+                Severity::Error,
+                header,
+                "Type=Application requires an Exec key unless DBusActivatable=true".to_string(),
+                SPEC_KEYS,
+            );
+        }
+        if kind == "Link" && main.get("URL").is_none_or(Value::is_empty) {
+            self.push(
+                Severity::Error,
+                header,
+                "Type=Link requires a URL key".to_string(),
+                SPEC_KEYS,
+            );
+        }
+
+        for e in main.entries() {
+            self.main_key(e, &kind, line(self, &e.full_key()));
+        }
+
+        if let Some(version) = entry.get_str("Version")
+            && !VERSIONS.contains(&version)
+        {
+            self.push(
+                Severity::Warning,
+                line(self, "Version"),
+                format!("Version \"{version}\" is not a released spec version"),
+                SPEC_KEYS,
+            );
+        }
+
+        if main.get("OnlyShowIn").is_some() && main.get("NotShowIn").is_some() {
+            self.push(
+                Severity::Error,
+                line(self, "NotShowIn"),
+                "OnlyShowIn and NotShowIn must not both be set".to_string(),
+                SPEC_KEYS,
+            );
+        }
+        for key in ["OnlyShowIn", "NotShowIn"] {
+            for desktop in entry.get_list(key).unwrap_or_default() {
+                if !DESKTOPS.contains(&desktop.as_str()) && !desktop.starts_with("X-") {
+                    self.push(
+                        Severity::Warning,
+                        line(self, key),
+                        format!("{desktop} in {key} is not a registered desktop environment"),
+                        SPEC_CATEGORIES,
+                    );
+                }
+            }
+        }
+
+        if let Some(name) = entry.get_str("Name")
+            && entry.get_str("Comment") == Some(name)
+        {
+            self.push(
+                Severity::Hint,
+                line(self, "Comment"),
+                "Comment should describe the launcher, not repeat Name".to_string(),
+                SPEC_KEYS,
+            );
+        }
+
+        if let Some(icon) = entry.get_str("Icon") {
+            self.icon(icon, line(self, "Icon"));
+        } else if kind == "Application" {
+            self.push(
+                Severity::Hint,
+                header,
+                "no Icon, menus will show a generic one".to_string(),
+                SPEC_KEYS,
+            );
+        }
+
+        if kind == "Application" {
+            self.application(entry, main, header);
+        }
+    }
+
+    /// Checks that apply to a single key of the main group.
+    fn main_key(&mut self, e: &Entry, kind: &str, line: Option<usize>) {
+        let key = e.key.as_str();
+
+        if key.starts_with("X-") {
+            return;
+        }
+        if DEPRECATED_KEYS.contains(&key) {
+            self.push(
+                Severity::Warning,
+                line,
+                format!("key \"{key}\" is deprecated"),
+                SPEC_KEYS,
+            );
+            return;
+        }
+        let Some((_, types)) = KEYS.iter().find(|(k, _)| *k == key) else {
+            let hint = if key == "Category" {
+                ", use Categories"
+            } else {
+                ", extensions must start with X-"
+            };
+            self.push(
+                Severity::Error,
+                line,
+                format!("unknown key \"{key}\"{hint}"),
+                SPEC_KEYS,
+            );
+            return;
+        };
+
+        if !types.is_empty() && TYPES.contains(&kind) && !types.contains(&kind) {
+            self.push(
+                Severity::Warning,
+                line,
+                format!("key \"{key}\" is only used with Type={}", types.join(", ")),
+                SPEC_KEYS,
+            );
+        }
+
+        self.value(e, line);
+    }
+
+    /// Type checks shared by the main group and action groups.
+    fn value(&mut self, e: &Entry, line: Option<usize>) {
+        let key = e.key.as_str();
+        let value_type = ValueType::of(key);
+
+        if e.locale.is_some()
+            && !matches!(
+                value_type,
+                ValueType::LocaleString | ValueType::LocaleStringList | ValueType::IconString
+            )
+        {
+            self.push(
+                Severity::Error,
+                line,
+                format!("key \"{key}\" cannot be localized"),
+                SPEC_LOCALE,
+            );
+        }
+
+        if value_type == ValueType::Boolean && e.value.as_bool().is_none() {
+            self.push(
+                Severity::Error,
+                line,
+                format!(
+                    "value of \"{key}\" must be true or false, found \"{}\"",
+                    e.value
+                ),
+                SPEC_VALUES,
+            );
+        }
+
+        if value_type.is_list()
+            && let Some(raw) = e.raw.as_deref().and_then(|raw| raw.split_once('='))
+        {
+            let raw = raw.1.trim();
+            if !raw.is_empty() && (!raw.ends_with(';') || raw.ends_with("\\;")) {
+                self.push(
+                    Severity::Warning,
+                    line,
+                    format!("list value of \"{}\" should end with ';'", e.full_key()),
+                    SPEC_VALUES,
+                );
+            }
+        }
+
+        if let Value::String(s) = &e.value
+            && s.chars()
+                .any(|c| c.is_control() && c != '\n' && c != '\t' && c != '\r')
+        {
+            self.push(
+                Severity::Error,
+                line,
+                format!("value of \"{key}\" contains control characters"),
+                SPEC_VALUES,
+            );
+        }
+    }
+
+    fn icon(&mut self, icon: &str, line: Option<usize>) {
+        let has_ext = ["png", "svg", "xpm"]
+            .iter()
+            .any(|ext| icon.to_ascii_lowercase().ends_with(&format!(".{ext}")));
+        if has_ext && !icon.starts_with('/') {
+            self.push(
+                Severity::Warning,
+                line,
+                format!("icon name \"{icon}\" should not include an extension"),
+                SPEC_ICONS,
+            );
+        }
+    }
+
+    fn exec(&mut self, key: &str, value: &str, line: Option<usize>) {
+        if let Err(e) = exec::parse(value) {
+            self.push(Severity::Error, line, format!("{key}: {e}"), SPEC_EXEC);
+        }
+    }
+
+    fn application(&mut self, entry: &DesktopEntry, main: &Group, header: Option<usize>) {
+        let line = |lint: &Self, key: &str| lint.line(MAIN_GROUP, key).or(header);
+
+        if let Some(value) = entry.get_str("Exec") {
+            self.exec("Exec", value, line(self, "Exec"));
+        }
+
+        match entry.get_list("Categories") {
+            None | Some([]) => self.push(
+                Severity::Hint,
+                header,
+                "no Categories, menus will file this launcher under Other".to_string(),
+                SPEC_CATEGORIES,
+            ),
+            Some(list) => {
+                let at = line(self, "Categories");
+                for message in categories::check(list) {
+                    self.push(Severity::Warning, at, message, SPEC_CATEGORIES);
+                }
+                for category in list.iter().filter(|c| categories::is_reserved(c)) {
+                    if main.get("OnlyShowIn").is_none() {
+                        self.push(
+                            Severity::Error,
+                            at,
+                            format!("reserved category {category} requires OnlyShowIn"),
+                            SPEC_CATEGORIES,
+                        );
+                    }
+                }
+            }
+        }
+
+        for mime in entry.get_list("MimeType").unwrap_or_default() {
+            if !is_mime_type(mime) {
+                self.push(
+                    Severity::Warning,
+                    line(self, "MimeType"),
+                    format!("\"{mime}\" is not a MIME type"),
+                    SPEC_KEYS,
+                );
+            }
+        }
+
+        let listed = actions::read(entry);
+        let at = line(self, "Actions");
+        for message in actions::check(&listed) {
+            self.push(Severity::Error, at, message, SPEC_ACTIONS);
+        }
+        for action in &listed {
+            if entry.group(&actions::group_name(&action.id)).is_none() {
+                self.push(
+                    Severity::Error,
+                    at,
+                    format!(
+                        "action {} has no [{}] group",
+                        action.id,
+                        actions::group_name(&action.id)
+                    ),
+                    SPEC_ACTIONS,
+                );
+            }
+        }
+    }
+
+    fn action_group(
+        &mut self,
+        entry: &DesktopEntry,
+        group: &Group,
+        id: &str,
+        header: Option<usize>,
+    ) {
+        let listed = entry
+            .get_list("Actions")
+            .unwrap_or_default()
+            .iter()
+            .any(|a| a == id);
+        if !listed {
+            self.push(
+                Severity::Warning,
+                header,
+                format!("[{}] is not listed in Actions", group.name),
+                SPEC_ACTIONS,
+            );
+        }
+
+        for e in group.entries() {
+            let line = self.line(&group.name, &e.full_key());
+            match e.key.as_str() {
+                "Name" | "Icon" => self.value(e, line),
+                "Exec" => {
+                    self.value(e, line);
+                    if let Some(value) = e.value.as_str() {
+                        self.exec("Exec", value, line);
+                    }
+                }
+                key if key.starts_with("X-") => {}
+                key => self.push(
+                    Severity::Error,
+                    line,
+                    format!("unknown key \"{key}\" in action group"),
+                    SPEC_ACTIONS,
+                ),
+            }
+        }
+    }
+}
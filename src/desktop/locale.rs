@@ -0,0 +1,149 @@
+use crate::desktop::entry::DesktopEntry;
+use crate::desktop::parser::is_valid_locale;
+use crate::desktop::value::Value;
+
+use std::env;
+
+/// One `Key[locale]=value` line of the main group. `value` is the text as
+/// written in the file, so lists like `Keywords` keep their separators.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Translation {
+    pub key: String,
+    pub locale: String,
+    pub value: String,
+}
+
+/// The locale for messages, from `$LC_ALL`, `$LC_MESSAGES` or `$LANG` like
+/// POSIX looks them up. `None` for the C locale.
+pub fn current() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| normalize(&value))
+}
+
+/// Turns an environment value like `de_DE.UTF-8@euro` into the form used
+/// in keys, `de_DE@euro`. The encoding is dropped as the spec says.
+/// `None` for `C` and `POSIX`, which mean untranslated.
+pub fn normalize(value: &str) -> Option<String> {
+    let (rest, modifier) = match value.trim().split_once('@') {
+        Some((rest, modifier)) => (rest, Some(modifier)),
+        None => (value.trim(), None),
+    };
+    let lang_country = rest.split_once('.').map_or(rest, |(lc, _)| lc);
+    if lang_country.is_empty() || matches!(lang_country, "C" | "POSIX") {
+        return None;
+    }
+    Some(match modifier {
+        Some(modifier) => format!("{lang_country}@{modifier}"),
+        None => lang_country.to_string(),
+    })
+}
+
+/// Keys to try for `locale`, best first: `lang_COUNTRY@MODIFIER`,
+/// `lang_COUNTRY`, `lang@MODIFIER`, `lang`. The unlocalized key comes
+/// after all of them.
+pub fn candidates(locale: &str) -> Vec<String> {
+    let (rest, modifier) = match locale.split_once('@') {
+        Some((rest, modifier)) => (rest, Some(modifier)),
+        None => (locale, None),
+    };
+    let rest = rest.split_once('.').map_or(rest, |(lc, _)| lc);
+    let (lang, country) = match rest.split_once('_') {
+        Some((lang, country)) => (lang, Some(country)),
+        None => (rest, None),
+    };
+
+    let mut candidates = Vec::new();
+    if let (Some(country), Some(modifier)) = (country, modifier) {
+        candidates.push(format!("{lang}_{country}@{modifier}"));
+    }
+    if let Some(country) = country {
+        candidates.push(format!("{lang}_{country}"));
+    }
+    if let Some(modifier) = modifier {
+        candidates.push(format!("{lang}@{modifier}"));
+    }
+    candidates.push(lang.to_string());
+    candidates
+}
+
+/// Reads the translations of `keys` from the main group, in file order.
+pub fn read(entry: &DesktopEntry, keys: &[&str]) -> Vec<Translation> {
+    let Some(group) = entry.main() else {
+        return Vec::new();
+    };
+    keys.iter()
+        .flat_map(|key| {
+            group
+                .translations(key)
+                .into_iter()
+                .map(|(locale, value)| Translation {
+                    key: key.to_string(),
+                    locale: locale.to_string(),
+                    value: value.to_raw(),
+                })
+        })
+        .collect()
+}
+
+/// Writes `translations` of `keys` into the main group. Translations of
+/// `keys` that are no longer listed are removed, the rest are changed in
+/// place. Ones without a locale are skipped.
+pub fn write(entry: &mut DesktopEntry, keys: &[&str], translations: &[Translation]) {
+    let group = entry.main_mut();
+    for key in keys {
+        let stale: Vec<String> = group
+            .translations(key)
+            .into_iter()
+            .map(|(locale, _)| locale.to_string())
+            .filter(|locale| {
+                !translations
+                    .iter()
+                    .any(|t| t.key == *key && t.locale == *locale)
+            })
+            .collect();
+        for locale in stale {
+            group.remove_localized(key, Some(&locale));
+        }
+    }
+
+    for translation in translations.iter().filter(|t| !t.locale.is_empty()) {
+        group.set_localized(
+            &translation.key,
+            Some(&translation.locale),
+            Value::parse(&translation.key, &translation.value),
+        );
+    }
+}
+
+/// Problems that would make the translations invalid once written.
+pub fn check(translations: &[Translation]) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    for (idx, translation) in translations.iter().enumerate() {
+        let label = format!("{}[{}]", translation.key, translation.locale);
+
+        if translation.locale.is_empty() {
+            warnings.push(format!(
+                "A translation of {} has no locale and will not be saved",
+                translation.key
+            ));
+            continue;
+        } else if !is_valid_locale(&translation.locale) {
+            warnings.push(format!("{label}: not a locale like de or pt_BR"));
+        } else if translations[..idx]
+            .iter()
+            .any(|t| t.key == translation.key && t.locale == translation.locale)
+        {
+            warnings.push(format!("{label}: duplicate locale"));
+        }
+
+        if translation.value.trim().is_empty() {
+            warnings.push(format!("{label} is empty"));
+        }
+    }
+
+    warnings
+}
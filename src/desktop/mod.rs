@@ -0,0 +1,15 @@
+pub mod actions;
+pub mod categories;
+pub mod entry;
+pub mod exec;
+pub mod id;
+pub mod lint;
+pub mod locale;
+pub mod parser;
+pub mod value;
+
+pub use entry::{DesktopEntry, Entry, Group, Line};
+pub use parser::{ParseError, ParseErrorKind};
+pub use value::{Value, ValueType};
+
+pub const MAIN_GROUP: &str = "Desktop Entry";
@@ -0,0 +1,175 @@
+use crate::desktop::MAIN_GROUP;
+use crate::desktop::entry::{DesktopEntry, Entry, Group, Line, split_locale};
+use crate::desktop::value::Value;
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A `key=value` line appeared before the first group header.
+    EntryOutsideGroup,
+    /// The first group of the file is not `[Desktop Entry]`.
+    MissingMainGroup(String),
+    InvalidGroupHeader,
+    DuplicateGroup(String),
+    DuplicateKey(String),
+    MissingEquals,
+    InvalidKey(String),
+    InvalidLocale(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::EntryOutsideGroup => write!(f, "key found before any group header"),
+            ParseErrorKind::MissingMainGroup(name) => {
+                write!(f, "first group must be [{MAIN_GROUP}], found [{name}]")
+            }
+            ParseErrorKind::InvalidGroupHeader => write!(f, "invalid group header"),
+            ParseErrorKind::DuplicateGroup(name) => write!(f, "duplicate group [{name}]"),
+            ParseErrorKind::DuplicateKey(key) => write!(f, "duplicate key \"{key}\""),
+            ParseErrorKind::MissingEquals => write!(f, "expected \"key=value\""),
+            ParseErrorKind::InvalidKey(key) => write!(f, "invalid key \"{key}\""),
+            ParseErrorKind::InvalidLocale(locale) => write!(f, "invalid locale \"{locale}\""),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a file following the freedesktop Desktop Entry Specification.
+///
+/// Comments, blank lines and the original text of every entry are kept so
+/// that serializing an untouched entry gives back the exact same bytes.
+pub fn parse(input: &str) -> Result<DesktopEntry, ParseError> {
+    parse_key_file(input, Some(MAIN_GROUP))
+}
+
+/// Parses any file in the key file format, such as an icon theme's
+/// `index.theme`. When `first_group` is set, the file must start with it.
+pub fn parse_key_file(input: &str, first_group: Option<&str>) -> Result<DesktopEntry, ParseError> {
+    let mut entry = DesktopEntry {
+        trailing_newline: input.ends_with('\n'),
+        ..DesktopEntry::default()
+    };
+
+    let body = input.strip_suffix('\n').unwrap_or(input);
+    let lines = if input.is_empty() {
+        Vec::new()
+    } else {
+        body.split('\n').collect()
+    };
+
+    for (idx, line) in lines.into_iter().enumerate() {
+        let number = idx + 1;
+        let err = |kind| ParseError { line: number, kind };
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            match entry.groups.last_mut() {
+                Some(group) => group.lines.push(Line::Raw(line.to_string())),
+                None => entry.preamble.push(line.to_string()),
+            }
+            continue;
+        }
+
+        if trimmed.starts_with('[') {
+            let name =
+                parse_group_header(trimmed).ok_or(err(ParseErrorKind::InvalidGroupHeader))?;
+
+            if let Some(first) = first_group
+                && entry.groups.is_empty()
+                && name != first
+            {
+                return Err(err(ParseErrorKind::MissingMainGroup(name.to_string())));
+            }
+            if entry.group(name).is_some() {
+                return Err(err(ParseErrorKind::DuplicateGroup(name.to_string())));
+            }
+
+            let mut group = Group::new(name);
+            group.header = Some(line.to_string());
+            entry.groups.push(group);
+            continue;
+        }
+
+        let Some(group) = entry.groups.last_mut() else {
+            return Err(err(ParseErrorKind::EntryOutsideGroup));
+        };
+
+        let Some((key, raw)) = trimmed.split_once('=') else {
+            return Err(err(ParseErrorKind::MissingEquals));
+        };
+
+        let full_key = key.trim_end();
+        let (key, locale) = split_locale(full_key);
+
+        if !is_valid_key(key) {
+            return Err(err(ParseErrorKind::InvalidKey(full_key.to_string())));
+        }
+        if let Some(locale) = locale
+            && !is_valid_locale(locale)
+        {
+            return Err(err(ParseErrorKind::InvalidLocale(locale.to_string())));
+        }
+        if group.get_localized(key, locale).is_some() {
+            return Err(err(ParseErrorKind::DuplicateKey(full_key.to_string())));
+        }
+
+        let mut parsed = Entry::new(key, locale, Value::parse(key, raw.trim_start()));
+        parsed.raw = Some(line.to_string());
+        group.lines.push(Line::Entry(parsed));
+    }
+
+    Ok(entry)
+}
+
+fn parse_group_header(line: &str) -> Option<&str> {
+    let name = line.strip_prefix('[')?.strip_suffix(']')?;
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii() && !c.is_ascii_control() && c != '[' && c != ']');
+    valid.then_some(name)
+}
+
+/// Keys may only contain `A-Za-z0-9-`.
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Accepts `lang_COUNTRY.ENCODING@MODIFIER` where everything but `lang` is optional.
+pub fn is_valid_locale(locale: &str) -> bool {
+    let (rest, modifier) = match locale.split_once('@') {
+        Some((rest, modifier)) => (rest, Some(modifier)),
+        None => (locale, None),
+    };
+    let (rest, encoding) = match rest.split_once('.') {
+        Some((rest, encoding)) => (rest, Some(encoding)),
+        None => (rest, None),
+    };
+    let (lang, country) = match rest.split_once('_') {
+        Some((lang, country)) => (lang, Some(country)),
+        None => (rest, None),
+    };
+
+    let word = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric());
+    word(lang)
+        && country.is_none_or(word)
+        && encoding.is_none_or(|e| !e.is_empty() && e.chars().all(|c| c != '@'))
+        && modifier.is_none_or(word)
+}
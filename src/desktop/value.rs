@@ -0,0 +1,177 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    String,
+    LocaleString,
+    IconString,
+    Boolean,
+    Numeric,
+    StringList,
+    LocaleStringList,
+}
+
+impl ValueType {
+    pub fn of(key: &str) -> Self {
+        match key {
+            "Name" | "GenericName" | "Comment" => ValueType::LocaleString,
+            "Icon" => ValueType::IconString,
+            "NoDisplay"
+            | "Hidden"
+            | "DBusActivatable"
+            | "Terminal"
+            | "StartupNotify"
+            | "PrefersNonDefaultGPU"
+            | "SingleMainWindow" => ValueType::Boolean,
+            "OnlyShowIn" | "NotShowIn" | "Actions" | "MimeType" | "Categories" | "Implements" => {
+                ValueType::StringList
+            }
+            "Keywords" => ValueType::LocaleStringList,
+            _ => ValueType::String,
+        }
+    }
+
+    pub fn is_list(self) -> bool {
+        matches!(self, ValueType::StringList | ValueType::LocaleStringList)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Boolean(bool),
+    Numeric(f64),
+    List(Vec<String>),
+}
+
+impl Value {
+    /// Decodes the raw text after `=` according to the type of `key`.
+    pub fn parse(key: &str, raw: &str) -> Self {
+        match ValueType::of(key) {
+            ValueType::Boolean => match raw {
+                "true" => Value::Boolean(true),
+                "false" => Value::Boolean(false),
+                _ => Value::String(unescape(raw)),
+            },
+            ValueType::Numeric => match raw.parse() {
+                Ok(n) => Value::Numeric(n),
+                Err(_) => Value::String(unescape(raw)),
+            },
+            ValueType::StringList | ValueType::LocaleStringList => Value::List(split_list(raw)),
+            _ => Value::String(unescape(raw)),
+        }
+    }
+
+    /// Encodes the value back into the text written after `=`.
+    pub fn to_raw(&self) -> String {
+        match self {
+            Value::String(s) => escape(s),
+            Value::Boolean(b) => b.to_string(),
+            Value::Numeric(n) => n.to_string(),
+            Value::List(items) => items
+                .iter()
+                .map(|item| format!("{};", escape(item).replace(';', "\\;")))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Value::String(s) => s.is_empty(),
+            Value::List(items) => items.is_empty(),
+            _ => false,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[String]> {
+        match self {
+            Value::List(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_raw())
+    }
+}
+
+pub fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            ' ' if i == 0 => out.push_str("\\s"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn unescape(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => out.push(' '),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Splits a `;` separated list, honouring `\;` escapes. The trailing
+/// separator is optional.
+pub fn split_list(raw: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(';') => current.push(';'),
+                Some(other) => {
+                    current.push('\\');
+                    current.push(other);
+                }
+                None => current.push('\\'),
+            },
+            ';' => items.push(unescape(&std::mem::take(&mut current))),
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        items.push(unescape(&current));
+    }
+    items
+}
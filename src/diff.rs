@@ -0,0 +1,68 @@
+/// How a line of the new text compares to the old one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    Same,
+    Added,
+    /// Takes the place of a removed line.
+    Changed,
+    /// Unchanged, with lines removed right before it, or right after it
+    /// when it is the last line.
+    Removed,
+}
+
+/// Marks every line of `new` against `old`, like the gutter of an editor.
+/// Lines are matched along a longest common subsequence. In a run of
+/// removed and added lines, added lines pair up with removed ones as
+/// changes.
+pub fn marks(old: &str, new: &str) -> Vec<Mark> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of
+    // old[i..] and new[j..].
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut marks = Vec::with_capacity(new.len());
+    let (mut i, mut j) = (0, 0);
+    // Removed lines of the current run not paired with an added one yet.
+    let mut removed = 0;
+    while j < new.len() {
+        if i < old.len() && old[i] == new[j] {
+            marks.push(if removed > 0 {
+                Mark::Removed
+            } else {
+                Mark::Same
+            });
+            removed = 0;
+            i += 1;
+            j += 1;
+        } else if i < old.len() && common[i + 1][j] >= common[i][j + 1] {
+            removed += 1;
+            i += 1;
+        } else if removed > 0 {
+            removed -= 1;
+            marks.push(Mark::Changed);
+            j += 1;
+        } else {
+            marks.push(Mark::Added);
+            j += 1;
+        }
+    }
+
+    if (i < old.len() || removed > 0)
+        && let Some(last) = marks.last_mut()
+        && *last == Mark::Same
+    {
+        *last = Mark::Removed;
+    }
+    marks
+}
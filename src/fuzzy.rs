@@ -0,0 +1,60 @@
+/// Bonus for a match right after the previous one.
+const CONSECUTIVE: i64 = 5;
+/// Bonus for a match at the start of a word.
+const WORD_START: i64 = 8;
+
+/// How well `query` matches `text` as a case-insensitive subsequence,
+/// higher is better. `None` when some character of the query is missing.
+/// Consecutive matches and matches at word starts score higher, skipped
+/// characters cost a point each.
+pub fn score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(lower)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let text: Vec<char> = text.chars().collect();
+
+    // Greedy matching can miss a better alignment that starts later, so
+    // try every position the first character matches at.
+    (0..text.len())
+        .filter(|&start| lower(text[start]) == query[0])
+        .filter_map(|start| score_from(&query, &text, start))
+        .max()
+}
+
+fn score_from(query: &[char], text: &[char], start: usize) -> Option<i64> {
+    let mut score = 0;
+    let mut last: Option<usize> = None;
+    let mut position = start;
+
+    for &wanted in query {
+        let found = (position..text.len()).find(|&i| lower(text[i]) == wanted)?;
+        score += 1;
+        if is_word_start(text, found) {
+            score += WORD_START;
+        }
+        match last {
+            Some(last) if found == last + 1 => score += CONSECUTIVE,
+            Some(last) => score -= (found - last - 1) as i64,
+            None => score -= found.min(10) as i64,
+        }
+        last = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+fn is_word_start(text: &[char], index: usize) -> bool {
+    let Some(previous) = index.checked_sub(1).map(|i| text[i]) else {
+        return true;
+    };
+    !previous.is_alphanumeric() || (previous.is_lowercase() && text[index].is_uppercase())
+}
+
+fn lower(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
@@ -0,0 +1,295 @@
+use crate::desktop::DesktopEntry;
+use crate::desktop::parser::parse_key_file;
+use crate::xdg;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+pub const FALLBACK_THEME: &str = "hicolor";
+pub const EXTENSIONS: [&str; 3] = ["png", "svg", "xpm"];
+/// Size used when nothing more specific is known; launchers are usually
+/// shown around this size.
+pub const DEFAULT_SIZE: u32 = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeType {
+    Fixed,
+    Scalable,
+    Threshold,
+}
+
+/// One subdirectory listed in a theme's `Directories=`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeDir {
+    pub path: String,
+    pub size: u32,
+    pub scale: u32,
+    pub kind: SizeType,
+    pub min_size: u32,
+    pub max_size: u32,
+    pub threshold: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    pub inherits: Vec<String>,
+    pub dirs: Vec<ThemeDir>,
+}
+
+impl ThemeDir {
+    fn matches_size(&self, size: u32, scale: u32) -> bool {
+        if self.scale != scale {
+            return false;
+        }
+        match self.kind {
+            SizeType::Fixed => self.size == size,
+            SizeType::Scalable => self.min_size <= size && size <= self.max_size,
+            SizeType::Threshold => {
+                self.size.saturating_sub(self.threshold) <= size
+                    && size <= self.size + self.threshold
+            }
+        }
+    }
+
+    fn size_distance(&self, size: u32, scale: u32) -> u32 {
+        let wanted = size * scale;
+        let (min, max) = match self.kind {
+            SizeType::Fixed => (self.size, self.size),
+            SizeType::Scalable => (self.min_size, self.max_size),
+            SizeType::Threshold => (
+                self.size.saturating_sub(self.threshold),
+                self.size + self.threshold,
+            ),
+        };
+        if wanted < min * self.scale {
+            min * self.scale - wanted
+        } else {
+            wanted.saturating_sub(max * self.scale)
+        }
+    }
+}
+
+impl Theme {
+    /// Reads a theme from the contents of its `index.theme`.
+    pub fn parse(name: &str, index: &str) -> Option<Self> {
+        let file: DesktopEntry = parse_key_file(index, None).ok()?;
+        let main = file.group("Icon Theme")?;
+        // Theme lists are comma separated, unlike desktop entry lists.
+        let list = |key: &str| -> Vec<String> {
+            main.get(key)
+                .map(|v| v.to_raw())
+                .unwrap_or_default()
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        };
+
+        let mut dir_names = list("Directories");
+        dir_names.extend(list("ScaledDirectories"));
+        let inherits = list("Inherits");
+
+        let dirs = dir_names
+            .iter()
+            .filter_map(|path| {
+                let group = file.group(path)?;
+                let num = |key: &str| {
+                    group
+                        .get(key)
+                        .and_then(|v| v.to_raw().trim().parse::<u32>().ok())
+                };
+                let size = num("Size")?;
+                let kind = match group.get("Type").map(|v| v.to_raw()).as_deref() {
+                    Some("Fixed") => SizeType::Fixed,
+                    Some("Scalable") => SizeType::Scalable,
+                    _ => SizeType::Threshold,
+                };
+                Some(ThemeDir {
+                    path: path.to_string(),
+                    size,
+                    scale: num("Scale").unwrap_or(1),
+                    kind,
+                    min_size: num("MinSize").unwrap_or(size),
+                    max_size: num("MaxSize").unwrap_or(size),
+                    threshold: num("Threshold").unwrap_or(2),
+                })
+            })
+            .collect();
+
+        Some(Self {
+            name: name.to_string(),
+            inherits,
+            dirs,
+        })
+    }
+}
+
+/// Looks up icon names the way the Icon Theme Specification describes:
+/// the current theme, the themes it inherits from, `hicolor`, and finally
+/// plain files in the base directories such as `/usr/share/pixmaps`.
+#[derive(Debug, Clone)]
+pub struct IconResolver {
+    pub base_dirs: Vec<PathBuf>,
+    pub theme: String,
+    /// Parsed `index.theme` files, loaded on first use.
+    themes: RefCell<HashMap<String, Option<Theme>>>,
+}
+
+impl IconResolver {
+    /// A resolver for the current user: `~/.icons`, `$XDG_DATA_HOME/icons`,
+    /// `$XDG_DATA_DIRS/icons` and `/usr/share/pixmaps`.
+    pub fn new() -> Self {
+        let mut base_dirs = vec![xdg::home_dir().join(".icons")];
+        base_dirs.extend(xdg::data_search_dirs().iter().map(|d| d.join("icons")));
+        base_dirs.push(PathBuf::from("/usr/share/pixmaps"));
+
+        Self::with_dirs(base_dirs, &current_theme())
+    }
+
+    pub fn with_dirs(base_dirs: Vec<PathBuf>, theme: &str) -> Self {
+        Self {
+            base_dirs,
+            theme: theme.to_string(),
+            themes: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Loads the first `index.theme` found for `name` in the base dirs.
+    fn theme(&self, name: &str) -> Option<Theme> {
+        self.themes
+            .borrow_mut()
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                self.base_dirs.iter().find_map(|dir| {
+                    let index = read_to_string(dir.join(name).join("index.theme")).ok()?;
+                    Theme::parse(name, &index)
+                })
+            })
+            .clone()
+    }
+
+    /// Resolves an `Icon=` value to a file. Absolute paths are checked as is.
+    pub fn lookup(&self, icon: &str, size: u32) -> Option<PathBuf> {
+        let icon = icon.trim();
+        if icon.is_empty() {
+            return None;
+        }
+
+        let path = Path::new(icon);
+        if path.is_absolute() {
+            return path.is_file().then(|| path.to_path_buf());
+        }
+
+        let mut visited = Vec::new();
+        if let Some(found) = self.lookup_in_theme(icon, size, &self.theme, &mut visited) {
+            return Some(found);
+        }
+        if let Some(found) = self.lookup_in_theme(icon, size, FALLBACK_THEME, &mut visited) {
+            return Some(found);
+        }
+
+        self.lookup_fallback(icon)
+    }
+
+    fn lookup_in_theme(
+        &self,
+        icon: &str,
+        size: u32,
+        theme: &str,
+        visited: &mut Vec<String>,
+    ) -> Option<PathBuf> {
+        if visited.iter().any(|t| t == theme) {
+            return None;
+        }
+        visited.push(theme.to_string());
+
+        let current = self.theme(theme)?;
+        if let Some(found) = self.lookup_icon(icon, size, 1, &current) {
+            return Some(found);
+        }
+
+        for parent in &current.inherits {
+            if let Some(found) = self.lookup_in_theme(icon, size, parent, visited) {
+                return Some(found);
+            }
+        }
+        None
+    }
+
+    fn lookup_icon(&self, icon: &str, size: u32, scale: u32, theme: &Theme) -> Option<PathBuf> {
+        for dir in theme.dirs.iter().filter(|d| d.matches_size(size, scale)) {
+            if let Some(found) = self.find_file(&theme.name, &dir.path, icon) {
+                return Some(found);
+            }
+        }
+
+        let mut closest: Option<(u32, PathBuf)> = None;
+        for dir in &theme.dirs {
+            let distance = dir.size_distance(size, scale);
+            if closest.as_ref().is_some_and(|(best, _)| distance >= *best) {
+                continue;
+            }
+            if let Some(found) = self.find_file(&theme.name, &dir.path, icon) {
+                closest = Some((distance, found));
+            }
+        }
+        closest.map(|(_, path)| path)
+    }
+
+    fn find_file(&self, theme: &str, subdir: &str, icon: &str) -> Option<PathBuf> {
+        self.base_dirs.iter().find_map(|base| {
+            let dir = base.join(theme).join(subdir);
+            candidates(icon).find_map(|file| {
+                let path = dir.join(file);
+                path.is_file().then_some(path)
+            })
+        })
+    }
+
+    /// Icons placed directly in a base directory, outside any theme.
+    fn lookup_fallback(&self, icon: &str) -> Option<PathBuf> {
+        self.base_dirs.iter().find_map(|base| {
+            candidates(icon).find_map(|file| {
+                let path = base.join(file);
+                path.is_file().then_some(path)
+            })
+        })
+    }
+}
+
+impl Default for IconResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// File names to try for `icon`. Names written with an extension, which the
+/// spec discourages but launchers accept, are also tried as given.
+fn candidates(icon: &str) -> impl Iterator<Item = String> + '_ {
+    let has_ext = Path::new(icon)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| EXTENSIONS.contains(&e));
+    has_ext
+        .then(|| icon.to_string())
+        .into_iter()
+        .chain(EXTENSIONS.iter().map(move |ext| format!("{icon}.{ext}")))
+}
+
+/// The icon theme configured for GTK, or `hicolor` when none is set.
+pub fn current_theme() -> String {
+    let settings = xdg::config_home().join("gtk-3.0/settings.ini");
+    read_to_string(settings)
+        .ok()
+        .and_then(|content| {
+            content.lines().find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == "gtk-icon-theme-name").then(|| value.trim().to_string())
+            })
+        })
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| FALLBACK_THEME.to_string())
+}
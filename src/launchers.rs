@@ -0,0 +1,255 @@
+use crate::desktop::DesktopEntry;
+use crate::desktop::lint::{self, Diagnostic, Severity};
+use crate::xdg;
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{canonicalize, read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+
+/// A `.desktop` file found on disk, with the results of checking it.
+#[derive(Debug, Clone)]
+pub struct Launcher {
+    /// The desktop file ID, e.g. `org.gnome.Nautilus.desktop`.
+    pub id: String,
+    pub path: PathBuf,
+    /// The launcher with the same ID that takes precedence over this one.
+    pub shadowed_by: Option<PathBuf>,
+    /// Empty when the file could not be read or parsed.
+    pub entry: DesktopEntry,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Valid,
+    Warnings,
+    Invalid,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Valid => "valid",
+            Status::Warnings => "warnings",
+            Status::Invalid => "invalid",
+        }
+    }
+}
+
+impl Launcher {
+    pub fn load(path: &Path) -> Self {
+        let id = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self::load_with_id(path, id)
+    }
+
+    pub fn load_with_id(path: &Path, id: String) -> Self {
+        let (entry, diagnostics) = match read_to_string(path) {
+            Ok(content) => match DesktopEntry::parse(&content) {
+                Ok(entry) => {
+                    let diagnostics = lint::check(&entry);
+                    (entry, diagnostics)
+                }
+                Err(e) => (DesktopEntry::default(), vec![lint::from_parse_error(&e)]),
+            },
+            Err(e) => (
+                DesktopEntry::default(),
+                vec![Diagnostic {
+                    severity: Severity::Error,
+                    line: None,
+                    message: e.to_string(),
+                    spec: lint::SPEC_FORMAT,
+                }],
+            ),
+        };
+
+        Self {
+            id,
+            path: path.to_path_buf(),
+            shadowed_by: None,
+            entry,
+            diagnostics,
+        }
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn text(&self, key: &str) -> &str {
+        self.entry.get_str(key).unwrap_or_default()
+    }
+
+    pub fn name(&self) -> &str {
+        self.text("Name")
+    }
+
+    /// `key` as a user with `locale` sees it, e.g. `Name[de]` for `de_DE`.
+    pub fn localized(&self, key: &str, locale: Option<&str>) -> &str {
+        self.entry
+            .get_for_locale(key, locale)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+    }
+
+    pub fn entry_type(&self) -> &str {
+        self.text("Type")
+    }
+
+    /// `URL` for links, `Exec` for everything else.
+    pub fn target(&self) -> &str {
+        match self.entry_type() {
+            "Link" => self.text("URL"),
+            _ => self.text("Exec"),
+        }
+    }
+
+    pub fn comment(&self) -> &str {
+        self.text("Comment")
+    }
+
+    pub fn icon(&self) -> &str {
+        self.text("Icon")
+    }
+
+    pub fn no_display(&self) -> bool {
+        self.entry.get_bool("NoDisplay").unwrap_or(false)
+    }
+
+    pub fn hidden(&self) -> bool {
+        self.entry.get_bool("Hidden").unwrap_or(false)
+    }
+
+    pub fn categories(&self) -> &[String] {
+        self.entry.get_list("Categories").unwrap_or_default()
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    }
+
+    pub fn status(&self) -> Status {
+        if self.count(Severity::Error) > 0 {
+            Status::Invalid
+        } else if self.count(Severity::Warning) > 0 {
+            Status::Warnings
+        } else {
+            Status::Valid
+        }
+    }
+}
+
+/// Every `.desktop` file directly inside `dir`, sorted by file name.
+pub fn scan(dir: &Path) -> Vec<Launcher> {
+    let Ok(entries) = read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "desktop") && p.is_file())
+        .collect();
+    paths.sort();
+
+    paths.iter().map(|p| Launcher::load(p)).collect()
+}
+
+/// The desktop file ID of `path` inside the applications directory `base`:
+/// its relative path with `/` replaced by `-`.
+pub fn desktop_file_id(base: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(base).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("-"))
+}
+
+/// `.desktop` files under `dir`, subdirectories included.
+fn walk(dir: &Path, visited: &mut HashSet<PathBuf>, out: &mut Vec<PathBuf>) {
+    // Guards against symlink loops.
+    if !canonicalize(dir).is_ok_and(|real| visited.insert(real)) {
+        return;
+    }
+    let Ok(entries) = read_dir(dir) else {
+        return;
+    };
+
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            walk(&path, visited, out);
+        } else if path.extension().is_some_and(|ext| ext == "desktop") && path.is_file() {
+            out.push(path);
+        }
+    }
+}
+
+/// Desktop file IDs and paths of every `.desktop` file in `dirs`, in order
+/// of precedence, without reading them.
+pub fn ids_in(dirs: &[PathBuf]) -> Vec<(String, PathBuf)> {
+    let mut found = Vec::new();
+    for base in dirs {
+        let mut paths = Vec::new();
+        walk(base, &mut HashSet::new(), &mut paths);
+        found.extend(
+            paths
+                .into_iter()
+                .filter_map(|path| Some((desktop_file_id(base, &path)?, path))),
+        );
+    }
+    found
+}
+
+/// The file `id` resolves to in `dirs`, i.e. the first one found.
+pub fn locate(id: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+    ids_in(dirs)
+        .into_iter()
+        .find(|(found, _)| found == id)
+        .map(|(_, path)| path)
+}
+
+/// Every launcher in `dirs`, in order of precedence. When an ID appears
+/// more than once, all but the first are marked as shadowed.
+pub fn scan_dirs(dirs: &[PathBuf]) -> Vec<Launcher> {
+    let mut seen: HashMap<String, PathBuf> = HashMap::new();
+
+    ids_in(dirs)
+        .into_iter()
+        .map(|(id, path)| {
+            let mut launcher = Launcher::load_with_id(&path, id.clone());
+            match seen.get(&id) {
+                Some(first) => launcher.shadowed_by = Some(first.clone()),
+                None => {
+                    seen.insert(id, path);
+                }
+            }
+            launcher
+        })
+        .collect()
+}
+
+/// Launchers from `$XDG_DATA_HOME`, every `$XDG_DATA_DIRS` entry and the
+/// Flatpak and Snap export directories.
+pub fn scan_all() -> Vec<Launcher> {
+    scan_dirs(&xdg::application_dirs())
+}
+
+/// Applications directories below the user's, where system launchers live.
+pub fn system_dirs() -> Vec<PathBuf> {
+    let user = xdg::applications_home();
+    xdg::application_dirs()
+        .into_iter()
+        .filter(|d| *d != user)
+        .collect()
+}
@@ -1 +1,10 @@
-pub mod cli;
\ No newline at end of file
+pub mod atomic;
+pub mod cli;
+pub mod desktop;
+pub mod diff;
+pub mod fuzzy;
+pub mod icons;
+pub mod launchers;
+pub mod references;
+pub mod trash;
+pub mod xdg;
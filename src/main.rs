@@ -1,94 +1,119 @@
 mod app;
+mod browser;
 mod cli;
 mod commands;
 mod utils;
 
-use crate::commands::remove::{remove, remove_err};
-use crate::{commands::edit::*, commands::list::list_all_desktop_files, commands::new::*};
+use crate::commands::browse::browse;
+use crate::commands::check::check;
+use crate::commands::hide::{hide, unhide};
+use crate::commands::keys::{get, set};
+use crate::commands::overrides::{override_launcher, reset};
+use crate::commands::remove::{remove, restore};
+use crate::commands::rename::{cp, mv};
+use crate::{
+    commands::edit::*,
+    commands::list::{Filter, list_all_desktop_files},
+    commands::new::*,
+};
 
 use app::App;
-use clap::{CommandFactory, Parser};
-use cli::Cli;
+use clap::Parser;
+use cli::{Cli, Command};
 use color_eyre::{Result, eyre::Ok};
-use dirs::data_dir;
+use deskforge::desktop::{id, locale};
+use deskforge::xdg;
 
 use std::fs::create_dir_all;
-use std::path::PathBuf;
 use std::process::exit;
 
-fn applications_dir() -> PathBuf {
-    let data_dir = match data_dir() {
-        Some(d) => d,
-        None => {
-            eprintln!("[ERROR]: Unexpected error");
-            exit(1);
-        }
-    };
-
-    if let Err(e) = create_dir_all(&data_dir) {
-        eprintln!("[ERROR]: Cannot create data_dir: {e}");
-        exit(1);
-    }
-
-    let app_dir = data_dir.join("applications");
-
-    if let Err(e) = create_dir_all(&app_dir) {
+/// Creates the user's applications directory so launchers can be saved.
+fn ensure_applications_dir() {
+    if let Err(e) = create_dir_all(xdg::applications_home()) {
         eprintln!("[ERROR]: Cannot create app_dir: {e}");
         exit(1);
     }
-
-    app_dir
-}
-
-fn normalize_desktop_name(input: &str) -> String {
-    let trimmed = input.trim();
-    if trimmed.ends_with(".desktop") {
-        trimmed.to_string()
-    } else {
-        format!("{trimmed}.desktop")
-    }
 }
 
+/// Whether the file the form would save a launcher called `name` to,
+/// under the ID it derives from the name, exists already.
 fn desktop_exists(name: &str) -> bool {
-    let trimmed = name.trim();
-    if trimmed.is_empty() {
-        return false;
-    }
-    let file_name = normalize_desktop_name(trimmed);
-    let file_path = applications_dir().join(&file_name);
-    file_path.exists()
+    let id = id::from_name(name);
+    !id.is_empty() && id::user_path(&id).exists()
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
     let cli = Cli::parse();
 
-    if cli.list {
-        list_all_desktop_files();
-        return Ok(());
-    }
-
-    match cli.remove {
+    match cli.command {
         None => {}
-        Some(name) => {
-            let file_name = normalize_desktop_name(&name);
-
-            if remove_err(&file_name) {
-                eprintln!("[ERROR]: File doesn't exist!");
+        Some(Command::New(args)) => {
+            ensure_applications_dir();
+            let path = id::user_path(&args.file_name);
+            exit(new_headless(*args, &path));
+        }
+        Some(Command::Get(args)) => exit(get(args)),
+        Some(Command::Set(args)) => exit(set(args)),
+        Some(Command::Override(args)) => {
+            let file_name = match override_launcher(&args.id) {
+                std::result::Result::Ok(file_name) => file_name,
+                Err(code) => exit(code),
+            };
+            if args.no_edit {
+                return Ok(());
+            }
+            if let Some(e) = parse_err(&file_name) {
+                eprintln!("[ERROR]: {file_name}: {e}");
                 exit(1)
             }
+            return edit(file_name);
+        }
+        Some(Command::Reset { id }) => exit(reset(&id)),
+        Some(Command::Hide(args)) => exit(hide(args)),
+        Some(Command::Unhide { id }) => exit(unhide(&id)),
+        Some(Command::Cp(args)) => exit(cp(args)),
+        Some(Command::Mv(args)) => exit(mv(args)),
+        Some(Command::Restore { name }) => exit(restore(name.as_deref())),
+    }
+
+    if cli.list {
+        let filter = Filter {
+            entry_type: cli.entry_type,
+            category: cli.category,
+            hidden: match (cli.hidden, cli.visible) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+        };
+        let locale = match &cli.locale {
+            Some(value) => locale::normalize(value),
+            None => locale::current(),
+        };
+        list_all_desktop_files(cli.all, cli.format, cli.sort, &filter, locale.as_deref());
+        return Ok(());
+    }
 
-            remove(&file_name);
-            return Ok(());
+    if let Some(names) = cli.check {
+        if !check(&names) {
+            exit(1);
         }
+        return Ok(());
+    }
+
+    if let Some(names) = cli.remove {
+        exit(remove(&names, cli.yes));
     }
 
     match cli.new {
         None => {}
         Some(None) => {
+            ensure_applications_dir();
             return new_default_file();
         }
         Some(Some(name)) => {
+            ensure_applications_dir();
             if desktop_exists(&name) {
                 eprintln!("[ERROR]: File name already exists!");
                 exit(1);
@@ -98,16 +123,19 @@ fn main() -> Result<()> {
     }
 
     if let Some(name) = cli.edit {
-        let file_name = normalize_desktop_name(&name);
+        let file_name = id::normalize(&name);
 
         if edit_err(&file_name) {
             eprintln!("[ERROR]: File doesn't exist!");
             exit(1)
         }
 
+        if let Some(e) = parse_err(&file_name) {
+            eprintln!("[ERROR]: {file_name}: {e}");
+            exit(1)
+        }
+
         return edit(file_name);
     }
-    eprintln!("[WARNING]: Wrong command!");
-    Cli::command().print_help().unwrap();
-    exit(1);
+    browse()
 }
@@ -0,0 +1,63 @@
+use crate::xdg;
+
+use std::env;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+/// A file outside the applications directory that names a launcher by its
+/// desktop file ID, and stops working when the ID changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub path: PathBuf,
+    /// 1-based line of the reference, `None` when the file name is the
+    /// reference, as for autostart entries.
+    pub line: Option<usize>,
+}
+
+/// The user's `mimeapps.list` files, most important first, following the
+/// Association between MIME types and applications specification.
+pub fn mimeapps_files() -> Vec<PathBuf> {
+    let config = xdg::config_home();
+    let mut files: Vec<PathBuf> = env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .split(':')
+        .filter(|d| !d.is_empty())
+        .map(|d| config.join(format!("{}-mimeapps.list", d.to_lowercase())))
+        .collect();
+    files.push(config.join("mimeapps.list"));
+    // Deprecated location, still read by most desktops.
+    files.push(xdg::data_home().join("applications/mimeapps.list"));
+    files
+}
+
+/// Where `id` is used as a default or added or removed association, and
+/// whether an autostart entry of the same ID exists.
+pub fn find(id: &str) -> Vec<Reference> {
+    let mut found = Vec::new();
+
+    for path in mimeapps_files() {
+        let Ok(content) = read_to_string(&path) else {
+            continue;
+        };
+        for (index, line) in content.lines().enumerate() {
+            let Some((_, value)) = line.split_once('=') else {
+                continue;
+            };
+            if value.split(';').any(|item| item.trim() == id) {
+                found.push(Reference {
+                    path: path.clone(),
+                    line: Some(index + 1),
+                });
+            }
+        }
+    }
+
+    let autostart = xdg::config_home().join("autostart").join(id);
+    if autostart.is_file() {
+        found.push(Reference {
+            path: autostart,
+            line: None,
+        });
+    }
+    found
+}
@@ -0,0 +1,203 @@
+use crate::desktop::parser::parse_key_file;
+use crate::xdg;
+
+use chrono::Local;
+
+use std::fs::{self, DirBuilder, OpenOptions, read_dir, read_to_string};
+use std::io::{self, ErrorKind, Write};
+use std::os::unix::fs::DirBuilderExt;
+use std::path::{Path, PathBuf};
+
+pub const INFO_GROUP: &str = "Trash Info";
+pub const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// A file in the trash together with where it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashedFile {
+    /// Name inside `files/`, which can differ from the original name.
+    pub name: String,
+    pub original: PathBuf,
+    /// `YYYY-MM-DDThh:mm:ss` in local time, sorts chronologically.
+    pub deleted: String,
+}
+
+/// A trash directory following the FreeDesktop.org Trash specification.
+#[derive(Debug, Clone)]
+pub struct Trash {
+    pub root: PathBuf,
+}
+
+impl Trash {
+    /// The user's home trash, `$XDG_DATA_HOME/Trash`.
+    pub fn home() -> Self {
+        Self::new(xdg::data_home().join("Trash"))
+    }
+
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn files(&self) -> PathBuf {
+        self.root.join("files")
+    }
+
+    fn info(&self) -> PathBuf {
+        self.root.join("info")
+    }
+
+    fn info_path(&self, name: &str) -> PathBuf {
+        self.info().join(format!("{name}.trashinfo"))
+    }
+
+    /// Moves `path` into the trash. The `.trashinfo` file is created first,
+    /// exclusively, which is how the spec reserves a name in `files/`.
+    pub fn put(&self, path: &Path) -> io::Result<TrashedFile> {
+        let original = std::path::absolute(path)?;
+        let file_name = original
+            .file_name()
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, "path has no file name"))?
+            .to_string_lossy()
+            .into_owned();
+        // The spec wants the trash readable by its owner only.
+        let mut dirs = DirBuilder::new();
+        dirs.mode(0o700).recursive(true);
+        dirs.create(self.files())?;
+        dirs.create(self.info())?;
+
+        let deleted = Local::now().format(DATE_FORMAT).to_string();
+        let info = format!(
+            "[{INFO_GROUP}]\nPath={}\nDeletionDate={deleted}\n",
+            encode_path(&original)
+        );
+
+        let mut counter = 1;
+        let name = loop {
+            let name = numbered(&file_name, counter);
+            let created = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.info_path(&name));
+            match created {
+                Ok(mut file) => {
+                    file.write_all(info.as_bytes())?;
+                    break name;
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => counter += 1,
+                Err(e) => return Err(e),
+            }
+        };
+
+        if let Err(e) = move_file(&original, &self.files().join(&name)) {
+            let _ = fs::remove_file(self.info_path(&name));
+            return Err(e);
+        }
+
+        Ok(TrashedFile {
+            name,
+            original,
+            deleted,
+        })
+    }
+
+    /// Everything in the trash that has a readable `.trashinfo`.
+    pub fn list(&self) -> Vec<TrashedFile> {
+        let Ok(entries) = read_dir(self.info()) else {
+            return Vec::new();
+        };
+
+        let mut items: Vec<TrashedFile> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let name = path
+                    .file_name()?
+                    .to_str()?
+                    .strip_suffix(".trashinfo")?
+                    .to_string();
+                let parsed = parse_key_file(&read_to_string(&path).ok()?, Some(INFO_GROUP)).ok()?;
+                let group = parsed.group(INFO_GROUP)?;
+                let original = decode_path(&group.get("Path")?.to_raw())?;
+                let deleted = group
+                    .get("DeletionDate")
+                    .map(|v| v.to_raw())
+                    .unwrap_or_default();
+                self.files().join(&name).exists().then_some(TrashedFile {
+                    name,
+                    original,
+                    deleted,
+                })
+            })
+            .collect();
+        items.sort_by(|a, b| a.deleted.cmp(&b.deleted).then(a.name.cmp(&b.name)));
+        items
+    }
+
+    /// Moves a file back to where it was deleted from. Fails with
+    /// `AlreadyExists` rather than replacing a file that took its place.
+    pub fn restore(&self, item: &TrashedFile) -> io::Result<()> {
+        if item.original.exists() {
+            return Err(io::Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} already exists", item.original.display()),
+            ));
+        }
+        if let Some(parent) = item.original.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        move_file(&self.files().join(&item.name), &item.original)?;
+        fs::remove_file(self.info_path(&item.name))
+    }
+}
+
+/// `foo.desktop`, `foo.2.desktop`, `foo.3.desktop`, ...
+fn numbered(file_name: &str, counter: usize) -> String {
+    if counter == 1 {
+        return file_name.to_string();
+    }
+    match file_name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!("{stem}.{counter}.{ext}"),
+        _ => format!("{file_name}.{counter}"),
+    }
+}
+
+/// Renames, falling back to copy and delete across filesystems.
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from).inspect_err(|_| {
+        let _ = fs::remove_file(to);
+    })
+}
+
+/// Percent-encodes a path the way `.trashinfo` files store it.
+pub fn encode_path(path: &Path) -> String {
+    let mut out = String::new();
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+pub fn decode_path(encoded: &str) -> Option<PathBuf> {
+    let bytes = encoded.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = encoded.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok().map(PathBuf::from)
+}
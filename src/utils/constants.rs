@@ -1,20 +1,11 @@
-pub const NUM_BLOCK: usize = 13;
+pub const ACTION_FIELDS: [&str; 4] = ["Id", "Name", "Exec", "Icon"];
+pub const TRANSLATION_FIELDS: [&str; 2] = ["Locale", "Value"];
 
-pub const IDX_NAME: usize = 0;
-pub const IDX_EXEC: usize = 1;
-pub const IDX_URL: usize = 1;
-pub const IDX_ICON: usize = 2;
-pub const IDX_VERSION: usize = 3;
-pub const IDX_COMMENT: usize = 4;
-pub const IDX_ACTION: usize = 5;
-pub const IDX_NODISPLAY: usize = 6;
-pub const IDX_STARTUPNOTIFY: usize = 7;
-pub const IDX_TERMINAL: usize = 8;
-pub const IDX_TYPE: usize = 9;
-pub const IDX_CATEGORY: usize = 10;
-pub const IDX_SAVE: usize = 11;
-pub const IDX_CANCEL: usize = 12;
+pub const SMALLEST_WIDTH: u16 = 30;
+pub const SMALLEST_HEIGHT: u16 = 12;
+pub const HALF_SCREEN: u16 = 89;
 
-pub const SMALLEST_WIDTH: u16 = 41;
-pub const SMALLEST_HEIGHT: u16 = 18;
-pub const HALF_SCREEN: u16 = 89;
\ No newline at end of file
+/// Exit codes of the non-interactive commands. 2 is taken by usage errors.
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_INVALID: i32 = 3;
+pub const EXIT_EXISTS: i32 = 4;
@@ -0,0 +1,77 @@
+use std::env;
+use std::path::PathBuf;
+
+/// Reads a colon separated list of absolute paths from `var`, falling back to
+/// `default` when it is unset or empty. Relative entries are ignored as the
+/// base directory spec requires.
+fn path_list(var: &str, default: &str) -> Vec<PathBuf> {
+    let value = env::var_os(var)
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| default.into());
+    env::split_paths(&value)
+        .filter(|p| p.is_absolute())
+        .collect()
+}
+
+pub fn home_dir() -> PathBuf {
+    dirs::home_dir().unwrap_or_else(|| PathBuf::from("/tmp"))
+}
+
+/// `$XDG_DATA_HOME`, usually `~/.local/share`.
+pub fn data_home() -> PathBuf {
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .unwrap_or_else(|| home_dir().join(".local/share"))
+}
+
+/// The user's applications directory, where launchers are saved.
+pub fn applications_home() -> PathBuf {
+    data_home().join("applications")
+}
+
+/// `$XDG_CONFIG_HOME`, usually `~/.config`.
+pub fn config_home() -> PathBuf {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .unwrap_or_else(|| home_dir().join(".config"))
+}
+
+/// `$XDG_DATA_DIRS`, in order of preference.
+pub fn data_dirs() -> Vec<PathBuf> {
+    path_list("XDG_DATA_DIRS", "/usr/local/share:/usr/share")
+}
+
+/// `$XDG_DATA_HOME` followed by `$XDG_DATA_DIRS`, without duplicates.
+pub fn data_search_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![data_home()];
+    for dir in data_dirs() {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Export directories of Flatpak and Snap. They are normally part of
+/// `$XDG_DATA_DIRS` already, but are added in case the session missed them.
+pub fn export_dirs() -> Vec<PathBuf> {
+    vec![
+        data_home().join("flatpak/exports/share"),
+        PathBuf::from("/var/lib/flatpak/exports/share"),
+        PathBuf::from("/var/lib/snapd/desktop"),
+    ]
+}
+
+/// Every `applications` directory, most important first: a desktop file ID
+/// found in an earlier directory hides the same ID in later ones.
+pub fn application_dirs() -> Vec<PathBuf> {
+    let mut dirs = data_search_dirs();
+    for dir in export_dirs() {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs.into_iter().map(|d| d.join("applications")).collect()
+}
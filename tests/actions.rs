@@ -0,0 +1,92 @@
+mod common;
+
+use common::fixture;
+use deskforge::desktop::DesktopEntry;
+use deskforge::desktop::actions::{self, Action};
+
+fn action(id: &str, name: &str, exec: &str) -> Action {
+    Action {
+        id: id.into(),
+        name: name.into(),
+        exec: exec.into(),
+        icon: String::new(),
+    }
+}
+
+#[test]
+fn read_existing_groups() {
+    let entry = DesktopEntry::parse(&fixture("firefox.desktop")).unwrap();
+
+    assert_eq!(
+        actions::read(&entry),
+        [
+            action("new-window", "Open a New Window", "firefox -new-window"),
+            action(
+                "new-private-window",
+                "Open a New Private Window",
+                "firefox -private-window"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn writing_read_actions_changes_nothing() {
+    let original = fixture("firefox.desktop");
+    let mut entry = DesktopEntry::parse(&original).unwrap();
+
+    let read = actions::read(&entry);
+    actions::write(&mut entry, &read);
+    assert_eq!(entry.serialize(), original);
+}
+
+#[test]
+fn write_creates_list_and_groups() {
+    let mut entry = DesktopEntry::parse("[Desktop Entry]\nName=App\n").unwrap();
+
+    actions::write(&mut entry, &[action("edit", "Edit", "app --edit")]);
+    assert_eq!(
+        entry.serialize(),
+        "[Desktop Entry]\nName=App\nActions=edit;\n\n[Desktop Action edit]\nName=Edit\nExec=app --edit\n"
+    );
+}
+
+#[test]
+fn write_removes_dropped_actions() {
+    let mut entry = DesktopEntry::parse(&fixture("firefox.desktop")).unwrap();
+
+    actions::write(
+        &mut entry,
+        &[action(
+            "new-window",
+            "Open a New Window",
+            "firefox -new-window",
+        )],
+    );
+    assert_eq!(entry.get_list("Actions").unwrap(), ["new-window"]);
+    assert!(entry.group("Desktop Action new-private-window").is_none());
+    assert!(entry.group("Desktop Action new-window").is_some());
+
+    actions::write(&mut entry, &[]);
+    assert!(entry.get("Actions").is_none());
+    assert_eq!(entry.groups.len(), 1);
+    assert!(entry.serialize().ends_with("StartupNotify=true\n"));
+}
+
+#[test]
+fn check_flags_bad_ids_and_missing_names() {
+    let warnings = actions::check(&[
+        action("new window", "New", ""),
+        action("a", "", ""),
+        action("a", "A", ""),
+    ]);
+
+    assert_eq!(
+        warnings,
+        [
+            "new window: id may only contain A-Z, a-z, 0-9 and -",
+            "a: Name is required",
+            "a: duplicate id",
+        ]
+    );
+}
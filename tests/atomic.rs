@@ -0,0 +1,47 @@
+use deskforge::atomic;
+
+use std::fs;
+use std::io::ErrorKind;
+
+#[test]
+fn create_refuses_to_overwrite_and_write_replaces() {
+    let dir = std::env::temp_dir().join(format!("deskforge-atomic-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("app.desktop");
+
+    atomic::create(&path, "first\n").unwrap();
+    let err = atomic::create(&path, "second\n").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "first\n");
+
+    atomic::write(&path, "third\n").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "third\n");
+
+    // No temporary files are left behind.
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn rename_never_replaces() {
+    let dir = std::env::temp_dir().join(format!("deskforge-atomic-mv-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let (a, b, c) = (
+        dir.join("a.desktop"),
+        dir.join("b.desktop"),
+        dir.join("c.desktop"),
+    );
+    fs::write(&a, "a").unwrap();
+    fs::write(&b, "b").unwrap();
+
+    let err = atomic::rename(&a, &b).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    assert_eq!(fs::read_to_string(&b).unwrap(), "b");
+
+    atomic::rename(&a, &c).unwrap();
+    assert!(!a.exists());
+    assert_eq!(fs::read_to_string(&c).unwrap(), "a");
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,65 @@
+use deskforge::desktop::{DesktopEntry, categories};
+
+#[test]
+fn legacy_category_is_migrated() {
+    let mut entry =
+        DesktopEntry::parse("[Desktop Entry]\nName=App\nCategory=Development\nType=Application\n")
+            .unwrap();
+
+    assert!(categories::migrate_legacy(&mut entry));
+    assert_eq!(
+        entry.serialize(),
+        "[Desktop Entry]\nName=App\nType=Application\nCategories=Development;\n"
+    );
+}
+
+#[test]
+fn legacy_category_merges_with_existing_list() {
+    let mut entry = DesktopEntry::parse(
+        "[Desktop Entry]\nCategories=Development;IDE;\nCategory=Development;Utility\n",
+    )
+    .unwrap();
+
+    categories::migrate_legacy(&mut entry);
+    assert_eq!(
+        entry.get_list("Categories").unwrap(),
+        ["Development", "IDE", "Utility"]
+    );
+    assert!(entry.get("Category").is_none());
+}
+
+#[test]
+fn empty_legacy_category_is_dropped() {
+    let mut entry = DesktopEntry::parse("[Desktop Entry]\nName=App\nCategory=\n").unwrap();
+
+    categories::migrate_legacy(&mut entry);
+    assert_eq!(entry.serialize(), "[Desktop Entry]\nName=App\n");
+}
+
+#[test]
+fn registry_lookups() {
+    assert!(categories::is_main("Development"));
+    assert!(categories::is_additional("IDE"));
+    assert_eq!(categories::related("IDE"), [["Development"]]);
+    assert!(categories::is_known("X-Custom"));
+    assert!(!categories::is_known("Category"));
+}
+
+#[test]
+fn check_reports_spec_rules() {
+    let list = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert!(categories::check(&list(&["Development", "IDE"])).is_empty());
+    assert_eq!(
+        categories::check(&list(&["Utility", "IDE"])),
+        ["IDE requires Development"]
+    );
+    assert_eq!(
+        categories::check(&list(&["Graphics", "VectorGraphics"])),
+        ["VectorGraphics requires Graphics+2DGraphics"]
+    );
+    assert_eq!(
+        categories::check(&list(&["Audio"])),
+        ["Audio should be used with AudioVideo"]
+    );
+}
@@ -0,0 +1,47 @@
+//! Fixtures and helpers for running the deskforge binary, shared by the
+//! integration tests.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// The contents of `tests/fixtures/{name}`.
+pub fn fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+    fs::read_to_string(path).unwrap()
+}
+
+/// A fresh temp dir for the test `name`, holding a data home (`home`), a
+/// config home (`config`) and a system data dir (`system`), each with an
+/// empty applications or autostart dir.
+pub fn setup(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("deskforge-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("home/applications")).unwrap();
+    fs::create_dir_all(root.join("config/autostart")).unwrap();
+    fs::create_dir_all(root.join("system/applications")).unwrap();
+    root
+}
+
+/// The deskforge binary, seeing only the dirs under `root`.
+pub fn command(root: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_deskforge"));
+    command
+        .env("XDG_DATA_HOME", root.join("home"))
+        .env("XDG_CONFIG_HOME", root.join("config"))
+        .env("XDG_DATA_DIRS", root.join("system"))
+        .env("LC_ALL", "C")
+        .env_remove("XDG_CURRENT_DESKTOP");
+    command
+}
+
+pub fn run(root: &Path, args: &[&str]) -> Output {
+    command(root).args(args).output().unwrap()
+}
+
+/// The standard output of a run that must succeed.
+pub fn stdout(output: Output) -> String {
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
@@ -0,0 +1,35 @@
+use deskforge::diff::{Mark, marks};
+
+const OLD: &str = "[Desktop Entry]\nType=Application\nName=Foo\nExec=foo\n";
+
+#[test]
+fn unchanged_text_has_no_marks() {
+    assert_eq!(marks(OLD, OLD), vec![Mark::Same; 4]);
+    assert!(marks("", "").is_empty());
+}
+
+#[test]
+fn marks_added_and_changed_lines() {
+    let new = "[Desktop Entry]\nType=Application\nName=Bar\nComment=A bar\nExec=foo\n";
+    assert_eq!(
+        marks(OLD, new),
+        vec![
+            Mark::Same,
+            Mark::Same,
+            Mark::Changed,
+            Mark::Added,
+            Mark::Same
+        ]
+    );
+
+    assert_eq!(marks("", "[Desktop Entry]\n"), vec![Mark::Added]);
+}
+
+#[test]
+fn marks_removed_lines_on_a_neighbour() {
+    let new = "[Desktop Entry]\nType=Application\nExec=foo\n";
+    assert_eq!(marks(OLD, new), vec![Mark::Same, Mark::Same, Mark::Removed]);
+
+    let new = "[Desktop Entry]\nType=Application\nName=Foo\n";
+    assert_eq!(marks(OLD, new), vec![Mark::Same, Mark::Same, Mark::Removed]);
+}
@@ -0,0 +1,111 @@
+use deskforge::desktop::exec::{self, ExecErrorKind, FieldCode};
+
+fn error(input: &str) -> (usize, ExecErrorKind) {
+    let err = exec::parse(input).unwrap_err();
+    (err.pos, err.kind)
+}
+
+#[test]
+fn splits_arguments_and_field_codes() {
+    let args = exec::parse("firefox --new-window %u").unwrap();
+
+    let texts: Vec<&str> = args.iter().map(|a| a.text.as_str()).collect();
+    assert_eq!(texts, ["firefox", "--new-window", "%u"]);
+    assert_eq!(args[2].start, 21);
+    assert_eq!(args[2].field_codes, [(FieldCode::Url, 21)]);
+}
+
+#[test]
+fn quoted_arguments() {
+    let args = exec::parse(r#"sh -c "echo \"hi\" \$HOME \\ \`x\`" %%"#).unwrap();
+
+    assert_eq!(args[2].text, r#"echo "hi" $HOME \ `x`"#);
+    assert!(args[2].quoted);
+    assert_eq!(args[3].text, "%%");
+    assert!(args[3].field_codes.is_empty());
+}
+
+#[test]
+fn program() {
+    assert_eq!(
+        exec::program(r#""/opt/My App/run" %F"#).as_deref(),
+        Some("/opt/My App/run")
+    );
+    assert_eq!(exec::program("a;b"), None);
+}
+
+#[test]
+fn rejects_invalid_and_deprecated_codes() {
+    assert_eq!(error("app %x"), (4, ExecErrorKind::InvalidFieldCode('x')));
+    assert_eq!(
+        error("app %d"),
+        (4, ExecErrorKind::DeprecatedFieldCode('d'))
+    );
+    assert_eq!(error("app %"), (4, ExecErrorKind::TrailingPercent));
+}
+
+#[test]
+fn rejects_mixed_target_codes() {
+    assert_eq!(
+        error("app %f %F"),
+        (7, ExecErrorKind::MultipleTargetCodes('f', 'F'))
+    );
+    assert_eq!(
+        error("app %u --open=%U"),
+        (14, ExecErrorKind::ListCodeNotAlone('U'))
+    );
+}
+
+#[test]
+fn quoting_rules() {
+    assert_eq!(
+        error(r#"app "unterminated"#),
+        (4, ExecErrorKind::UnterminatedQuote)
+    );
+    assert_eq!(error(r#"app "\n""#), (5, ExecErrorKind::InvalidEscape('n')));
+    assert_eq!(
+        error(r#"app "%f""#),
+        (5, ExecErrorKind::FieldCodeInQuotes('f'))
+    );
+    assert_eq!(error("app a&b"), (5, ExecErrorKind::UnquotedReserved('&')));
+    assert_eq!(error("  "), (0, ExecErrorKind::Empty));
+}
+
+#[test]
+fn resolves_through_path() {
+    use exec::Resolved;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    let root = std::env::temp_dir().join(format!("deskforge-path-{}", std::process::id()));
+    let (first, second) = (root.join("first"), root.join("second"));
+    fs::create_dir_all(&first).unwrap();
+    fs::create_dir_all(&second).unwrap();
+
+    fs::write(first.join("tool"), "").unwrap();
+    fs::set_permissions(first.join("tool"), fs::Permissions::from_mode(0o644)).unwrap();
+    fs::write(second.join("tool"), "").unwrap();
+    fs::set_permissions(second.join("tool"), fs::Permissions::from_mode(0o755)).unwrap();
+    fs::write(first.join("plain"), "").unwrap();
+    fs::set_permissions(first.join("plain"), fs::Permissions::from_mode(0o644)).unwrap();
+
+    let path_var = std::env::join_paths([&first, &second]).unwrap();
+
+    assert_eq!(
+        exec::resolve_in("tool", &path_var),
+        Resolved::Found(second.join("tool"))
+    );
+    assert_eq!(
+        exec::resolve_in("plain", &path_var),
+        Resolved::NotExecutable(first.join("plain"))
+    );
+    assert_eq!(exec::resolve_in("missing", &path_var), Resolved::NotFound);
+
+    let absolute = second.join("tool");
+    assert_eq!(
+        exec::resolve_in(absolute.to_str().unwrap(), &path_var),
+        Resolved::Found(absolute.clone())
+    );
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,25 @@
+use deskforge::fuzzy::score;
+
+#[test]
+fn matches_subsequences_ignoring_case() {
+    assert!(score("ffx", "Firefox").is_some());
+    assert!(score("FIRE", "firefox").is_some());
+    assert_eq!(score("xf", "Firefox"), None);
+    assert_eq!(score("", "anything"), Some(0));
+    assert!(score("gnome calc", "GNOME Calculator").is_some());
+}
+
+#[test]
+fn ranks_tighter_and_word_start_matches_higher() {
+    let substring = score("term", "Terminal").unwrap();
+    let scattered = score("term", "The Emacs Rendering Machine").unwrap();
+    assert!(substring > scattered);
+
+    let word_starts = score("gc", "GNOME Calculator").unwrap();
+    let middle = score("gc", "Magic").unwrap();
+    assert!(word_starts > middle);
+
+    let camel = score("vc", "VisualCode").unwrap();
+    let plain = score("vc", "Vivaciously").unwrap();
+    assert!(camel > plain);
+}
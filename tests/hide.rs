@@ -0,0 +1,61 @@
+mod common;
+
+use common::{fixture, run, stdout};
+use std::fs;
+use std::path::PathBuf;
+
+/// A data home with `link.desktop` and a system dir with `htop.desktop`.
+fn setup(name: &str) -> PathBuf {
+    let root = common::setup(&format!("hide-{name}"));
+    fs::write(
+        root.join("home/applications/link.desktop"),
+        fixture("link.desktop"),
+    )
+    .unwrap();
+    fs::write(
+        root.join("system/applications/htop.desktop"),
+        fixture("htop.desktop"),
+    )
+    .unwrap();
+    root
+}
+
+#[test]
+fn hides_system_launcher_through_override() {
+    let root = setup("system");
+    let user = root.join("home/applications/htop.desktop");
+
+    stdout(run(&root, &["hide", "htop"]));
+    let hidden = fs::read_to_string(&user).unwrap();
+    assert!(hidden.contains("NoDisplay=true\n"));
+    assert_eq!(
+        hidden.lines().count(),
+        fixture("htop.desktop").lines().count() + 1
+    );
+
+    let listed = stdout(run(&root, &["--list", "--hidden"]));
+    assert!(listed.contains("htop.desktop"));
+    assert!(!listed.contains("link.desktop"));
+    let listed = stdout(run(&root, &["--list", "--visible"]));
+    assert!(!listed.contains("htop.desktop"));
+
+    // Unhiding leaves the override identical to the system file, so it goes.
+    stdout(run(&root, &["unhide", "htop"]));
+    assert!(!user.exists());
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn toggles_flag_on_user_launcher() {
+    let root = setup("user");
+    let path = root.join("home/applications/link.desktop");
+
+    stdout(run(&root, &["hide", "link", "--hidden"]));
+    assert!(fs::read_to_string(&path).unwrap().contains("Hidden=true\n"));
+
+    stdout(run(&root, &["unhide", "link.desktop"]));
+    assert_eq!(fs::read_to_string(&path).unwrap(), fixture("link.desktop"));
+
+    fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,148 @@
+use deskforge::icons::{IconResolver, SizeType, Theme};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const HICOLOR: &str = "\
+[Icon Theme]
+Name=Hicolor
+Directories=48x48/apps,scalable/apps
+
+[48x48/apps]
+Size=48
+Type=Threshold
+
+[scalable/apps]
+Size=128
+MinSize=8
+MaxSize=512
+Type=Scalable
+";
+
+const CUSTOM: &str = "\
+[Icon Theme]
+Name=Custom
+Inherits=Base
+Directories=16x16/apps,64x64/apps
+
+[16x16/apps]
+Size=16
+Type=Fixed
+
+[64x64/apps]
+Size=64
+Type=Fixed
+";
+
+const BASE: &str = "\
+[Icon Theme]
+Name=Base
+Inherits=Custom,hicolor
+Directories=32x32/apps
+
+[32x32/apps]
+Size=32
+";
+
+fn touch(path: &Path) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "").unwrap();
+}
+
+fn tree(name: &str) -> (PathBuf, IconResolver) {
+    let root = std::env::temp_dir().join(format!("deskforge-icons-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let icons = root.join("icons");
+    let pixmaps = root.join("pixmaps");
+
+    for (theme, index) in [("hicolor", HICOLOR), ("Custom", CUSTOM), ("Base", BASE)] {
+        fs::create_dir_all(icons.join(theme)).unwrap();
+        fs::write(icons.join(theme).join("index.theme"), index).unwrap();
+    }
+    touch(&icons.join("hicolor/48x48/apps/firefox.png"));
+    touch(&icons.join("hicolor/scalable/apps/firefox.svg"));
+    touch(&icons.join("hicolor/scalable/apps/inkscape.svg"));
+    touch(&icons.join("Custom/16x16/apps/term.png"));
+    touch(&icons.join("Custom/64x64/apps/term.png"));
+    touch(&icons.join("Base/32x32/apps/base-only.xpm"));
+    touch(&pixmaps.join("legacy.xpm"));
+
+    let resolver = IconResolver::with_dirs(vec![icons, pixmaps], "Custom");
+    (root, resolver)
+}
+
+#[test]
+fn parses_index_theme() {
+    let theme = Theme::parse("hicolor", HICOLOR).unwrap();
+    assert!(theme.inherits.is_empty());
+    assert_eq!(theme.dirs.len(), 2);
+    assert_eq!(theme.dirs[0].kind, SizeType::Threshold);
+    assert_eq!(theme.dirs[0].threshold, 2);
+    assert_eq!(theme.dirs[1].kind, SizeType::Scalable);
+    assert_eq!((theme.dirs[1].min_size, theme.dirs[1].max_size), (8, 512));
+
+    let base = Theme::parse("Base", BASE).unwrap();
+    assert_eq!(base.inherits, ["Custom", "hicolor"]);
+}
+
+#[test]
+fn prefers_exact_then_closest_size() {
+    let (root, resolver) = tree("size");
+    let icons = root.join("icons");
+
+    assert_eq!(
+        resolver.lookup("firefox", 48),
+        Some(icons.join("hicolor/48x48/apps/firefox.png"))
+    );
+    assert_eq!(
+        resolver.lookup("firefox", 256),
+        Some(icons.join("hicolor/scalable/apps/firefox.svg"))
+    );
+    assert_eq!(
+        resolver.lookup("term", 48),
+        Some(icons.join("Custom/64x64/apps/term.png"))
+    );
+    assert_eq!(
+        resolver.lookup("term", 16),
+        Some(icons.join("Custom/16x16/apps/term.png"))
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn follows_inherits_and_falls_back() {
+    let (root, resolver) = tree("inherits");
+
+    assert_eq!(
+        resolver.lookup("base-only", 48),
+        Some(root.join("icons/Base/32x32/apps/base-only.xpm"))
+    );
+    assert_eq!(
+        resolver.lookup("inkscape", 48),
+        Some(root.join("icons/hicolor/scalable/apps/inkscape.svg"))
+    );
+    assert_eq!(
+        resolver.lookup("legacy", 48),
+        Some(root.join("pixmaps/legacy.xpm"))
+    );
+    assert_eq!(resolver.lookup("missing", 48), None);
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn accepts_paths_and_extensions() {
+    let (root, resolver) = tree("paths");
+    let legacy = root.join("pixmaps/legacy.xpm");
+
+    assert_eq!(resolver.lookup("legacy.xpm", 48), Some(legacy.clone()));
+    assert_eq!(
+        resolver.lookup(legacy.to_str().unwrap(), 48),
+        Some(legacy.clone())
+    );
+    assert_eq!(resolver.lookup("/nonexistent/icon.png", 48), None);
+    assert_eq!(resolver.lookup("", 48), None);
+
+    fs::remove_dir_all(root).unwrap();
+}
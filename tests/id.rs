@@ -0,0 +1,35 @@
+use deskforge::desktop::id::{check, from_name, normalize};
+use deskforge::desktop::lint::Severity;
+
+fn severities(id: &str) -> Vec<Severity> {
+    check(id).into_iter().map(|d| d.severity).collect()
+}
+
+#[test]
+fn ids_from_names() {
+    assert_eq!(from_name("My Cool App/Beta"), "my-cool-app-beta.desktop");
+    assert_eq!(from_name("  Émacs -- 30 "), "macs-30.desktop");
+    assert_eq!(from_name("org.example.App"), "org.example.App.desktop");
+    assert_eq!(from_name("app.desktop"), "app.desktop");
+    assert_eq!(from_name("Version 2.0"), "version-2-0.desktop");
+    assert_eq!(from_name("???"), "");
+    assert_eq!(normalize(" app "), "app.desktop");
+}
+
+#[test]
+fn dbus_naming_rules() {
+    assert!(check("org.example.App.desktop").is_empty());
+    assert!(check("org.example.my_app").is_empty());
+    assert_eq!(severities("firefox.desktop"), [Severity::Hint]);
+
+    assert_eq!(severities(".desktop"), [Severity::Error]);
+    assert_eq!(severities("apps/foo.desktop"), [Severity::Error]);
+
+    assert_eq!(severities("org.example.My App"), [Severity::Warning]);
+    assert_eq!(severities("org..App"), [Severity::Warning]);
+    assert_eq!(severities("org.7zip"), [Severity::Warning]);
+    assert_eq!(
+        severities(&format!("org.{}", "a".repeat(255))),
+        [Severity::Warning]
+    );
+}
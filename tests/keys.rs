@@ -0,0 +1,143 @@
+mod common;
+
+use common::{fixture, run, stdout};
+use std::fs;
+use std::path::PathBuf;
+
+/// A data home with a copy of the firefox fixture.
+fn setup(name: &str) -> PathBuf {
+    let root = common::setup(&format!("keys-{name}"));
+    fs::write(
+        root.join("home/applications/firefox.desktop"),
+        fixture("firefox.desktop"),
+    )
+    .unwrap();
+    root
+}
+
+#[test]
+fn get_reads_any_group_and_locale() {
+    let root = setup("get");
+
+    assert_eq!(
+        stdout(run(&root, &["get", "firefox", "Exec"])),
+        "firefox %u\n"
+    );
+    assert_eq!(
+        stdout(run(
+            &root,
+            &[
+                "get",
+                "firefox",
+                "Name",
+                "--group",
+                "Desktop Action new-window"
+            ]
+        )),
+        "Open a New Window\n"
+    );
+
+    assert_eq!(
+        stdout(run(&root, &["get", "firefox", "Name[de]"])),
+        "Firefox-Webbrowser\n"
+    );
+
+    let missing = run(&root, &["get", "firefox.desktop", "Nope"]);
+    assert_eq!(missing.status.code(), Some(1));
+    assert!(missing.stdout.is_empty());
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn set_only_touches_changed_lines() {
+    let root = setup("set");
+    let path = root.join("home/applications/firefox.desktop");
+    let original = fixture("firefox.desktop");
+
+    stdout(run(
+        &root,
+        &["set", "firefox", "Exec=firefox --private %u", "X-Test=yes"],
+    ));
+    let changed = fs::read_to_string(&path).unwrap();
+    let expected = original.replacen("Exec=firefox %u", "Exec=firefox --private %u", 1);
+    assert!(changed.starts_with(expected.split("\n\n").next().unwrap()));
+    assert!(changed.contains("X-Test=yes\n"));
+    assert_eq!(changed.lines().count(), original.lines().count() + 1);
+
+    stdout(run(
+        &root,
+        &["set", "firefox", "--unset", "X-Test", "Exec=firefox %u"],
+    ));
+    assert_eq!(fs::read_to_string(&path).unwrap(), original);
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn set_action_group_and_refuse_invalid() {
+    let root = setup("group");
+    let path = root.join("home/applications/firefox.desktop");
+
+    stdout(run(
+        &root,
+        &[
+            "set",
+            "firefox",
+            "--group",
+            "Desktop Action new-window",
+            "Icon=firefox-new",
+        ],
+    ));
+    assert_eq!(
+        stdout(run(
+            &root,
+            &[
+                "get",
+                "firefox",
+                "Icon",
+                "--group",
+                "Desktop Action new-window"
+            ]
+        )),
+        "firefox-new\n"
+    );
+
+    let before = fs::read_to_string(&path).unwrap();
+    let invalid = run(&root, &["set", "firefox", "Exec=firefox %d"]);
+    assert_eq!(invalid.status.code(), Some(3));
+    let bad_key = run(&root, &["set", "firefox", "Bad Key=1"]);
+    assert_eq!(bad_key.status.code(), Some(1));
+    assert_eq!(fs::read_to_string(&path).unwrap(), before);
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn get_follows_locale() {
+    let root = setup("get-locale");
+
+    assert_eq!(
+        stdout(run(
+            &root,
+            &["get", "firefox", "Name", "--locale", "de_DE.UTF-8"]
+        )),
+        "Firefox-Webbrowser\n"
+    );
+    // No translation to Dutch, so the untranslated Name.
+    assert_eq!(
+        stdout(run(&root, &["get", "firefox", "Name", "--locale", "nl"])),
+        "Firefox Web Browser\n"
+    );
+
+    let output = common::command(&root)
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env("LANG", "fr_FR.UTF-8")
+        .args(["get", "firefox", "Name"])
+        .output()
+        .unwrap();
+    assert_eq!(stdout(output), "Navigateur Web Firefox\n");
+
+    fs::remove_dir_all(root).unwrap();
+}
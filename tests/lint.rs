@@ -0,0 +1,105 @@
+mod common;
+
+use common::fixture;
+use deskforge::desktop::DesktopEntry;
+use deskforge::desktop::lint::{self, Diagnostic, Severity};
+
+fn find<'a>(diagnostics: &'a [Diagnostic], needle: &str) -> &'a Diagnostic {
+    diagnostics
+        .iter()
+        .find(|d| d.message.contains(needle))
+        .unwrap_or_else(|| panic!("no diagnostic containing {needle:?} in {diagnostics:#?}"))
+}
+
+#[test]
+fn valid_files_have_no_errors() {
+    for name in ["firefox.desktop", "link.desktop", "steam-game.desktop"] {
+        let diagnostics = lint::lint(&fixture(name));
+        assert!(!lint::has_errors(&diagnostics), "{name}: {diagnostics:#?}");
+    }
+    assert!(lint::lint(&fixture("link.desktop")).is_empty());
+}
+
+#[test]
+fn reports_errors_with_lines() {
+    let diagnostics = lint::lint(&fixture("invalid.desktop"));
+    assert!(lint::has_errors(&diagnostics));
+
+    let cases = [
+        ("Comment should describe", Severity::Hint, 4),
+        ("deprecated field code %d", Severity::Error, 5),
+        ("\"Terminal\" must be true or false", Severity::Error, 6),
+        (
+            "unknown key \"Category\", use Categories",
+            Severity::Error,
+            7,
+        ),
+        ("IDE requires Development", Severity::Warning, 8),
+        (
+            "list value of \"Categories\" should end with ';'",
+            Severity::Warning,
+            8,
+        ),
+        ("\"Exec\" cannot be localized", Severity::Error, 9),
+        ("only used with Type=Link", Severity::Warning, 10),
+        ("\"Encoding\" is deprecated", Severity::Warning, 11),
+        ("new: Name is required", Severity::Error, 12),
+        ("must not both be set", Severity::Error, 14),
+        (
+            "unknown key \"Shortcut\" in action group",
+            Severity::Error,
+            18,
+        ),
+        ("unknown group [Extra]", Severity::Error, 20),
+    ];
+    for (needle, severity, line) in cases {
+        let d = find(&diagnostics, needle);
+        assert_eq!((d.severity, d.line), (severity, Some(line)), "{needle}");
+    }
+
+    let lines: Vec<usize> = diagnostics.iter().filter_map(|d| d.line).collect();
+    assert!(lines.is_sorted());
+}
+
+#[test]
+fn required_keys_per_type() {
+    let diagnostics = lint::lint("[Desktop Entry]\nType=Application\n");
+    find(&diagnostics, "required key \"Name\" is missing");
+    find(&diagnostics, "requires an Exec key");
+
+    let diagnostics =
+        lint::lint("[Desktop Entry]\nType=Application\nName=D\nDBusActivatable=true\n");
+    assert!(!lint::has_errors(&diagnostics), "{diagnostics:#?}");
+
+    let diagnostics = lint::lint("[Desktop Entry]\nType=Link\nName=L\n");
+    find(&diagnostics, "Type=Link requires a URL key");
+
+    let diagnostics = lint::lint(
+        "[Desktop Entry]\nType=Application\nName=T\nExec=t\nCategories=Utility;TrayIcon;\n",
+    );
+    find(
+        &diagnostics,
+        "reserved category TrayIcon requires OnlyShowIn",
+    );
+
+    let diagnostics = lint::lint("[Desktop Entry]\nType=Service\nName=S\n");
+    find(&diagnostics, "unknown Type \"Service\"");
+}
+
+#[test]
+fn parse_errors_become_diagnostics() {
+    let diagnostics = lint::lint("[Desktop Entry]\nName=A\nName=B\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, Some(3));
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "3: error: duplicate key \"Name\" [desktop-entry-spec 3 (Basic format)]"
+    );
+}
+
+#[test]
+fn checks_in_memory_entries() {
+    let mut entry = DesktopEntry::parse(&fixture("link.desktop")).unwrap();
+    entry.remove("URL");
+    find(&lint::check(&entry), "requires a URL key");
+}
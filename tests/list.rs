@@ -0,0 +1,130 @@
+mod common;
+
+use common::{fixture, run, stdout};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn setup(name: &str) -> PathBuf {
+    let root = common::setup(&format!("list-{name}"));
+    for name in [
+        "firefox.desktop",
+        "link.desktop",
+        "code.desktop",
+        "invalid.desktop",
+    ] {
+        fs::write(root.join("home/applications").join(name), fixture(name)).unwrap();
+    }
+    root
+}
+
+fn list(root: &Path, args: &[&str]) -> String {
+    stdout(run(root, &[&["--list"], args].concat()))
+}
+
+#[test]
+fn tsv_has_one_row_per_launcher() {
+    let root = setup("tsv");
+    let out = list(&root, &["--format", "tsv", "--sort", "name"]);
+    let rows: Vec<Vec<&str>> = out.lines().map(|l| l.split('\t').collect()).collect();
+
+    assert_eq!(rows[0][..4], ["path", "id", "name", "type"]);
+    let names: Vec<&str> = rows[1..].iter().map(|r| r[2]).collect();
+    assert_eq!(
+        names,
+        [
+            "Broken",
+            "Firefox Web Browser",
+            "Rust Documentation",
+            "Visual Studio Code"
+        ]
+    );
+    assert!(rows.iter().all(|r| r.len() == 11));
+
+    let link = &rows[3];
+    assert_eq!(link[1], "link.desktop");
+    assert_eq!(link[3], "Link");
+    assert_eq!(link[4], "https://doc.rust-lang.org/std/");
+    assert_eq!(link[9], "valid");
+    assert_eq!(rows[1][9], "invalid");
+    assert_eq!(rows[4][8], "TextEditor;Development;IDE");
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn json_with_filters() {
+    let root = setup("json");
+
+    let out = list(&root, &["--format", "json", "--type", "Link"]);
+    assert_eq!(out.matches("\"path\"").count(), 1);
+    assert!(out.contains("\"url\": \"https://doc.rust-lang.org/std/\""));
+    assert!(out.contains("\"categories\": []"));
+
+    let out = list(&root, &["--format", "json", "--category", "Network"]);
+    assert_eq!(out.matches("\"path\"").count(), 1);
+    assert!(out.contains("\"name\": \"Firefox Web Browser\""));
+    assert!(out.contains("\"categories\": [\"GNOME\", \"GTK\", \"Network\", \"WebBrowser\"]"));
+
+    let out = list(&root, &["--format", "json", "--category", "Nope"]);
+    assert_eq!(out.trim(), "[]");
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn all_follows_precedence_and_ids() {
+    let root = setup("all");
+    let system = root.join("system/applications");
+    fs::create_dir_all(system.join("kde")).unwrap();
+    fs::copy(
+        root.join("home/applications/firefox.desktop"),
+        system.join("firefox.desktop"),
+    )
+    .unwrap();
+    fs::copy(
+        root.join("home/applications/code.desktop"),
+        system.join("kde/konsole.desktop"),
+    )
+    .unwrap();
+
+    let out = list(&root, &["--all", "--format", "tsv"]);
+    let rows: Vec<Vec<&str>> = out
+        .lines()
+        .skip(1)
+        .map(|l| l.split('\t').collect())
+        .collect();
+    let find = |path: &Path| {
+        rows.iter()
+            .find(|r| Path::new(r[0]) == path)
+            .unwrap_or_else(|| panic!("{path:?} not listed in {out}"))
+    };
+
+    let user = find(&root.join("home/applications/firefox.desktop"));
+    assert_eq!((user[1], user[10]), ("firefox.desktop", "false"));
+    let system_firefox = find(&system.join("firefox.desktop"));
+    assert_eq!(
+        (system_firefox[1], system_firefox[10]),
+        ("firefox.desktop", "true")
+    );
+    let konsole = find(&system.join("kde/konsole.desktop"));
+    assert_eq!((konsole[1], konsole[10]), ("kde-konsole.desktop", "false"));
+
+    let text = list(&root, &["--all"]);
+    assert!(text.contains(&format!(
+        "firefox.desktop ({}) [shadowed]",
+        system.join("firefox.desktop").display()
+    )));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn names_follow_locale() {
+    let root = setup("locale");
+
+    let out = list(&root, &["--format", "tsv", "--locale", "de_AT.UTF-8"]);
+    let firefox = out.lines().find(|l| l.contains("firefox.desktop")).unwrap();
+    assert_eq!(firefox.split('\t').nth(2), Some("Firefox-Webbrowser"));
+
+    fs::remove_dir_all(root).unwrap();
+}
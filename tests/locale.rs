@@ -0,0 +1,104 @@
+mod common;
+
+use common::fixture;
+use deskforge::desktop::DesktopEntry;
+use deskforge::desktop::locale::{self, Translation};
+
+fn translation(key: &str, locale: &str, value: &str) -> Translation {
+    Translation {
+        key: key.into(),
+        locale: locale.into(),
+        value: value.into(),
+    }
+}
+
+#[test]
+fn normalize_drops_encoding() {
+    assert_eq!(locale::normalize("de_DE.UTF-8").as_deref(), Some("de_DE"));
+    assert_eq!(
+        locale::normalize("sr_RS.UTF-8@latin").as_deref(),
+        Some("sr_RS@latin")
+    );
+    assert_eq!(locale::normalize("pt").as_deref(), Some("pt"));
+    assert_eq!(locale::normalize("C.UTF-8"), None);
+    assert_eq!(locale::normalize("POSIX"), None);
+    assert_eq!(locale::normalize(""), None);
+}
+
+#[test]
+fn candidates_in_spec_order() {
+    assert_eq!(
+        locale::candidates("sr_RS@latin"),
+        ["sr_RS@latin", "sr_RS", "sr@latin", "sr"]
+    );
+    assert_eq!(locale::candidates("de_DE"), ["de_DE", "de"]);
+    assert_eq!(locale::candidates("fr"), ["fr"]);
+}
+
+#[test]
+fn get_for_locale_falls_back() {
+    let entry = DesktopEntry::parse(&fixture("firefox.desktop")).unwrap();
+    let name = |locale| {
+        entry
+            .get_for_locale("Name", locale)
+            .and_then(|v| v.as_str())
+            .unwrap()
+            .to_string()
+    };
+
+    assert_eq!(name(Some("de_CH")), "Firefox-Webbrowser");
+    assert_eq!(name(Some("fr")), "Navigateur Web Firefox");
+    assert_eq!(name(Some("nl_NL")), "Firefox Web Browser");
+    assert_eq!(name(None), "Firefox Web Browser");
+}
+
+#[test]
+fn writing_read_translations_changes_nothing() {
+    let original = fixture("firefox.desktop");
+    let mut entry = DesktopEntry::parse(&original).unwrap();
+
+    let read = locale::read(&entry, &["Name", "Comment"]);
+    assert_eq!(read[0], translation("Name", "de", "Firefox-Webbrowser"));
+    locale::write(&mut entry, &["Name", "Comment"], &read);
+    assert_eq!(entry.serialize(), original);
+}
+
+#[test]
+fn write_adds_and_removes_translations() {
+    let mut entry =
+        DesktopEntry::parse("[Desktop Entry]\nName=App\nName[fr]=Appli\nExec=app\n").unwrap();
+
+    locale::write(
+        &mut entry,
+        &["Name"],
+        &[
+            translation("Name", "de", "Anwendung"),
+            translation("Name", "", "skipped"),
+        ],
+    );
+    assert_eq!(
+        entry.serialize(),
+        "[Desktop Entry]\nName=App\nName[de]=Anwendung\nExec=app\n"
+    );
+}
+
+#[test]
+fn check_flags_bad_translations() {
+    let warnings = locale::check(&[
+        translation("Name", "de", "App"),
+        translation("Name", "de", "Noch eine"),
+        translation("Name", "no locale!", "x"),
+        translation("Comment", "fr", " "),
+        translation("Comment", "", ""),
+    ]);
+
+    assert_eq!(
+        warnings,
+        [
+            "Name[de]: duplicate locale",
+            "Name[no locale!]: not a locale like de or pt_BR",
+            "Comment[fr] is empty",
+            "A translation of Comment has no locale and will not be saved",
+        ]
+    );
+}
@@ -0,0 +1,193 @@
+mod common;
+
+use deskforge::desktop::DesktopEntry;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A data home without an applications dir, which `new` creates.
+fn setup(name: &str) -> PathBuf {
+    let root = common::setup(&format!("new-{name}"));
+    fs::remove_dir(root.join("home/applications")).unwrap();
+    root
+}
+
+fn deskforge(root: &Path, args: &[&str]) -> i32 {
+    common::run(root, args).status.code().unwrap()
+}
+
+#[test]
+fn creates_launcher_from_flags() {
+    let root = setup("flags");
+    let code = deskforge(
+        &root,
+        &[
+            "new",
+            "myapp",
+            "--exec",
+            "myapp --open %F",
+            "--icon",
+            "myapp",
+            "--categories",
+            "Development;IDE",
+            "--terminal",
+        ],
+    );
+    assert_eq!(code, 0);
+
+    let content = fs::read_to_string(root.join("home/applications/myapp.desktop")).unwrap();
+    let entry = DesktopEntry::parse(&content).unwrap();
+    assert_eq!(entry.get_str("Name"), Some("myapp"));
+    assert_eq!(entry.get_str("Exec"), Some("myapp --open %F"));
+    assert_eq!(entry.get_str("Type"), Some("Application"));
+    assert_eq!(entry.get_bool("Terminal"), Some(true));
+    assert_eq!(
+        entry.get_list("Categories"),
+        Some(&["Development".to_string(), "IDE".to_string()][..])
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn exit_codes() {
+    let root = setup("codes");
+    let path = root.join("home/applications/app.desktop");
+
+    assert_eq!(deskforge(&root, &["new", "app", "--exec", "app %d"]), 3);
+    assert!(!path.exists());
+    assert_eq!(deskforge(&root, &["new", "app"]), 3);
+    assert_eq!(deskforge(&root, &["new", "apps/app", "--exec", "app"]), 3);
+
+    assert_eq!(deskforge(&root, &["new", "app", "--exec", "app"]), 0);
+    assert_eq!(deskforge(&root, &["new", "app", "--exec", "other"]), 4);
+    assert!(fs::read_to_string(&path).unwrap().contains("Exec=app\n"));
+
+    assert_eq!(
+        deskforge(&root, &["new", "app.desktop", "--exec", "other", "--force"]),
+        0
+    );
+    assert!(fs::read_to_string(&path).unwrap().contains("Exec=other\n"));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn writes_optional_keys() {
+    let root = setup("optional");
+    let code = deskforge(
+        &root,
+        &[
+            "new",
+            "viewer",
+            "--exec",
+            "viewer %f",
+            "--generic-name",
+            "Image Viewer",
+            "--keywords",
+            "Photo;Picture",
+            "--mime-type",
+            "image/png;image/jpeg",
+            "--try-exec",
+            "viewer",
+            "--path",
+            "/tmp",
+            "--wm-class",
+            "Viewer",
+        ],
+    );
+    assert_eq!(code, 0);
+
+    let content = fs::read_to_string(root.join("home/applications/viewer.desktop")).unwrap();
+    for line in [
+        "GenericName=Image Viewer\n",
+        "Keywords=Photo;Picture;\n",
+        "TryExec=viewer\n",
+        "Path=/tmp\n",
+        "StartupWMClass=Viewer\n",
+        "MimeType=image/png;image/jpeg;\n",
+    ] {
+        assert!(content.contains(line), "{line:?} missing from {content}");
+    }
+
+    // Links have no working directory.
+    assert_eq!(
+        deskforge(
+            &root,
+            &[
+                "new",
+                "docs",
+                "--type",
+                "Link",
+                "--url",
+                "https://example.com",
+                "--path",
+                "/tmp",
+            ]
+        ),
+        0
+    );
+    let content = fs::read_to_string(root.join("home/applications/docs.desktop")).unwrap();
+    assert!(!content.contains("Path="), "{content}");
+
+    // Exec belongs to applications only.
+    assert_eq!(
+        deskforge(
+            &root,
+            &["new", "folder", "--type", "Directory", "--exec", "folder"]
+        ),
+        0
+    );
+    let content = fs::read_to_string(root.join("home/applications/folder.desktop")).unwrap();
+    assert!(!content.contains("Exec="), "{content}");
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn startup_notify_has_its_own_flag() {
+    let root = setup("notify");
+    let read = |id: &str| {
+        let content = fs::read_to_string(root.join("home/applications").join(id)).unwrap();
+        DesktopEntry::parse(&content).unwrap()
+    };
+
+    // NoDisplay used to be written as StartupNotify.
+    assert_eq!(
+        deskforge(&root, &["new", "hidden", "--exec", "x", "--no-display"]),
+        0
+    );
+    let entry = read("hidden.desktop");
+    assert_eq!(entry.get_bool("NoDisplay"), Some(true));
+    assert_eq!(entry.get_bool("StartupNotify"), Some(true));
+
+    assert_eq!(
+        deskforge(
+            &root,
+            &["new", "quiet", "--exec", "x", "--no-startup-notify"]
+        ),
+        0
+    );
+    let entry = read("quiet.desktop");
+    assert_eq!(entry.get_bool("NoDisplay"), Some(false));
+    assert_eq!(entry.get_bool("StartupNotify"), Some(false));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn new_refuses_the_id_derived_from_the_name() {
+    let root = setup("derived");
+    let dir = root.join("home/applications");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("my-app.desktop"), "[Desktop Entry]\n").unwrap();
+
+    // The form would save "My App" as my-app.desktop, not "My App.desktop".
+    assert_eq!(deskforge(&root, &["--new", "My App"]), 1);
+    assert_eq!(
+        fs::read_to_string(dir.join("my-app.desktop")).unwrap(),
+        "[Desktop Entry]\n"
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
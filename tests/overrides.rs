@@ -0,0 +1,66 @@
+mod common;
+
+use common::{fixture, run};
+use std::fs;
+use std::path::PathBuf;
+
+/// A system data dir holding `messy.desktop` as
+/// `applications/vendor/messy.desktop`.
+fn setup(name: &str) -> PathBuf {
+    let root = common::setup(&format!("override-{name}"));
+    fs::create_dir_all(root.join("system/applications/vendor")).unwrap();
+    fs::write(
+        root.join("system/applications/vendor/messy.desktop"),
+        fixture("messy.desktop"),
+    )
+    .unwrap();
+    root
+}
+
+#[test]
+fn override_copies_losslessly_and_reset_removes() {
+    let root = setup("copy");
+    let user = root.join("home/applications/vendor-messy.desktop");
+
+    let out = run(&root, &["override", "vendor-messy", "--no-edit"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(fs::read_to_string(&user).unwrap(), fixture("messy.desktop"));
+
+    // Overriding again keeps the user's changes.
+    fs::write(&user, "[Desktop Entry]\nName=Mine\n").unwrap();
+    let out = run(&root, &["override", "vendor-messy.desktop", "--no-edit"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        fs::read_to_string(&user).unwrap(),
+        "[Desktop Entry]\nName=Mine\n"
+    );
+
+    let out = run(&root, &["reset", "vendor-messy"]);
+    assert!(out.status.success(), "{out:?}");
+    assert!(!user.exists());
+    assert!(
+        root.join("system/applications/vendor/messy.desktop")
+            .exists()
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn errors() {
+    let root = setup("errors");
+
+    let out = run(&root, &["override", "missing", "--no-edit"]);
+    assert_eq!(out.status.code(), Some(1));
+    let out = run(&root, &["reset", "vendor-messy"]);
+    assert_eq!(out.status.code(), Some(1));
+
+    // A launcher that only exists in the user's directory is not an override.
+    let own = root.join("home/applications/own.desktop");
+    fs::write(&own, fixture("link.desktop")).unwrap();
+    let out = run(&root, &["reset", "own"]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(own.exists());
+
+    fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use common::fixture;
+use deskforge::desktop::{DesktopEntry, ParseErrorKind, Value};
+
+fn parse_err(input: &str) -> (usize, ParseErrorKind) {
+    let err = DesktopEntry::parse(input).unwrap_err();
+    (err.line, err.kind)
+}
+
+#[test]
+fn action_groups_do_not_override_main_entry() {
+    let entry = DesktopEntry::parse(&fixture("firefox.desktop")).unwrap();
+
+    assert_eq!(entry.get_str("Name"), Some("Firefox Web Browser"));
+    assert_eq!(entry.get_str("Exec"), Some("firefox %u"));
+
+    let action = entry.group("Desktop Action new-window").unwrap();
+    assert_eq!(
+        action.get("Name"),
+        Some(&Value::String("Open a New Window".into()))
+    );
+    assert_eq!(
+        action.get("Exec"),
+        Some(&Value::String("firefox -new-window".into()))
+    );
+}
+
+#[test]
+fn localized_keys_are_kept_apart() {
+    let entry = DesktopEntry::parse(&fixture("firefox.desktop")).unwrap();
+    let main = entry.main().unwrap();
+
+    assert_eq!(
+        main.get_localized("Name", Some("de")),
+        Some(&Value::String("Firefox-Webbrowser".into()))
+    );
+    assert_eq!(
+        main.get("Name"),
+        Some(&Value::String("Firefox Web Browser".into()))
+    );
+}
+
+#[test]
+fn typed_values() {
+    let entry = DesktopEntry::parse(&fixture("code.desktop")).unwrap();
+
+    assert_eq!(entry.get_bool("StartupNotify"), Some(false));
+    assert_eq!(
+        entry.get_list("Categories").unwrap(),
+        ["TextEditor", "Development", "IDE"]
+    );
+    assert_eq!(entry.get_list("Keywords").unwrap(), ["vscode"]);
+    assert_eq!(entry.get_str("StartupWMClass"), Some("Code"));
+}
+
+#[test]
+fn comments_blank_lines_and_spaces_around_equals() {
+    let entry = DesktopEntry::parse(&fixture("htop.desktop")).unwrap();
+
+    assert_eq!(entry.get_str("Comment"), Some("Show System Processes"));
+    assert_eq!(entry.get_bool("Terminal"), Some(true));
+    assert_eq!(
+        entry.get_list("Keywords").unwrap(),
+        ["system", "process", "task"]
+    );
+}
+
+#[test]
+fn link_and_steam_launchers() {
+    let link = DesktopEntry::parse(&fixture("link.desktop")).unwrap();
+    assert_eq!(link.get_str("URL"), Some("https://doc.rust-lang.org/std/"));
+
+    let game = DesktopEntry::parse(&fixture("steam-game.desktop")).unwrap();
+    assert_eq!(game.get_str("Exec"), Some("steam steam://rungameid/620"));
+}
+
+#[test]
+fn escapes_in_strings_and_lists() {
+    let entry = DesktopEntry::parse("[Desktop Entry]\nComment=a\\sb\\nc\\\\d\nKeywords=x\\;y;z;\n")
+        .unwrap();
+
+    assert_eq!(entry.get_str("Comment"), Some("a b\nc\\d"));
+    assert_eq!(entry.get_list("Keywords").unwrap(), ["x;y", "z"]);
+}
+
+#[test]
+fn duplicate_key_reports_line() {
+    let (line, kind) = parse_err("[Desktop Entry]\nName=A\n\nName=B\n");
+    assert_eq!(line, 4);
+    assert_eq!(kind, ParseErrorKind::DuplicateKey("Name".into()));
+}
+
+#[test]
+fn same_key_in_other_group_is_not_a_duplicate() {
+    let input = "[Desktop Entry]\nName=A\n[Desktop Action x]\nName=B\n";
+    assert!(DesktopEntry::parse(input).is_ok());
+}
+
+#[test]
+fn duplicate_group() {
+    let (line, kind) = parse_err("[Desktop Entry]\n[Desktop Action a]\n[Desktop Action a]\n");
+    assert_eq!(line, 3);
+    assert_eq!(
+        kind,
+        ParseErrorKind::DuplicateGroup("Desktop Action a".into())
+    );
+}
+
+#[test]
+fn key_before_group() {
+    let (line, kind) = parse_err("# comment\nName=A\n[Desktop Entry]\n");
+    assert_eq!(line, 2);
+    assert_eq!(kind, ParseErrorKind::EntryOutsideGroup);
+}
+
+#[test]
+fn first_group_must_be_desktop_entry() {
+    let (line, kind) = parse_err("[Desktop Action a]\nName=A\n");
+    assert_eq!(line, 1);
+    assert_eq!(
+        kind,
+        ParseErrorKind::MissingMainGroup("Desktop Action a".into())
+    );
+}
+
+#[test]
+fn malformed_lines() {
+    assert_eq!(
+        parse_err("[Desktop Entry]\nName\n"),
+        (2, ParseErrorKind::MissingEquals)
+    );
+    assert_eq!(
+        parse_err("[Desktop Entry]\nMy Key=1\n"),
+        (2, ParseErrorKind::InvalidKey("My Key".into()))
+    );
+    assert_eq!(
+        parse_err("[Desktop Entry]\nName[]=A\n"),
+        (2, ParseErrorKind::InvalidLocale("".into()))
+    );
+    assert_eq!(
+        parse_err("[Desktop Entry\n"),
+        (1, ParseErrorKind::InvalidGroupHeader)
+    );
+}
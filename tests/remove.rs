@@ -0,0 +1,87 @@
+mod common;
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Output, Stdio};
+
+fn setup(name: &str) -> PathBuf {
+    let root = common::setup(&format!("remove-{name}"));
+    for file in ["steam-a.desktop", "steam-b.desktop", "other.desktop"] {
+        fs::write(root.join("home/applications").join(file), file).unwrap();
+    }
+    root
+}
+
+fn deskforge(root: &Path, args: &[&str], input: &str) -> Output {
+    let mut child = common::command(root)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn glob_asks_before_trashing() {
+    let root = setup("glob");
+    let apps = root.join("home/applications");
+
+    let out = deskforge(&root, &["--remove", "steam-*"], "n\n");
+    assert_eq!(out.status.code(), Some(1));
+    assert!(apps.join("steam-a.desktop").exists());
+
+    let out = deskforge(&root, &["--remove", "steam-*"], "y\n");
+    assert!(out.status.success(), "{out:?}");
+    assert!(!apps.join("steam-a.desktop").exists());
+    assert!(!apps.join("steam-b.desktop").exists());
+    assert!(apps.join("other.desktop").exists());
+    assert!(root.join("home/Trash/files/steam-a.desktop").exists());
+    assert!(
+        root.join("home/Trash/info/steam-b.desktop.trashinfo")
+            .exists()
+    );
+
+    let out = deskforge(&root, &["restore", "steam-a"], "");
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        fs::read_to_string(apps.join("steam-a.desktop")).unwrap(),
+        "steam-a.desktop"
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn single_and_missing_names() {
+    let root = setup("names");
+    let apps = root.join("home/applications");
+
+    let out = deskforge(&root, &["--remove", "other"], "");
+    assert!(out.status.success(), "{out:?}");
+    assert!(!apps.join("other.desktop").exists());
+
+    let out = deskforge(&root, &["--remove", "steam-a", "missing"], "");
+    assert_eq!(out.status.code(), Some(1));
+    assert!(apps.join("steam-a.desktop").exists());
+
+    let out = deskforge(&root, &["--remove", "steam-a", "steam-b", "-y"], "");
+    assert!(out.status.success(), "{out:?}");
+    assert!(!apps.join("steam-b.desktop").exists());
+
+    let out = deskforge(&root, &["restore", "missing"], "");
+    assert_eq!(out.status.code(), Some(1));
+    let out = deskforge(&root, &["restore"], "");
+    let listed = String::from_utf8(out.stdout).unwrap();
+    assert!(listed.contains("other.desktop") && listed.contains("Total: 3"));
+
+    fs::remove_dir_all(root).unwrap();
+}
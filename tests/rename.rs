@@ -0,0 +1,74 @@
+mod common;
+
+use common::run;
+use std::fs;
+use std::path::PathBuf;
+
+const APP: &str = "[Desktop Entry]\nType=Application\nName=Foo\nExec=sh\n";
+
+/// A system data dir holding `sys.desktop`.
+fn setup(name: &str) -> PathBuf {
+    let root = common::setup(&format!("rename-{name}"));
+    fs::write(root.join("system/applications/sys.desktop"), APP).unwrap();
+    root
+}
+
+#[test]
+fn cp_copies_system_launchers_under_a_new_id() {
+    let root = setup("cp");
+    let copy = root.join("home/applications/org.example.Mine.desktop");
+
+    let out = run(&root, &["cp", "sys", "org.example.Mine", "--name", "Mine"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        fs::read_to_string(&copy).unwrap(),
+        "[Desktop Entry]\nType=Application\nName=Mine\nExec=sh\n"
+    );
+
+    let out = run(&root, &["cp", "sys", "org.example.Mine"]);
+    assert_eq!(out.status.code(), Some(4));
+    let out = run(&root, &["cp", "missing", "other"]);
+    assert_eq!(out.status.code(), Some(1));
+    let out = run(&root, &["cp", "sys", "a/b"]);
+    assert_eq!(out.status.code(), Some(3));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn mv_renames_and_reports_stale_references() {
+    let root = setup("mv");
+    let apps = root.join("home/applications");
+    fs::write(apps.join("foo.desktop"), APP).unwrap();
+    fs::write(apps.join("taken.desktop"), APP).unwrap();
+    fs::write(
+        root.join("config/mimeapps.list"),
+        "[Default Applications]\ntext/plain=other.desktop;foo.desktop;\n",
+    )
+    .unwrap();
+    fs::write(root.join("config/autostart/foo.desktop"), APP).unwrap();
+
+    let out = run(&root, &["mv", "foo", "taken"]);
+    assert_eq!(out.status.code(), Some(4));
+    assert!(apps.join("foo.desktop").exists());
+
+    let out = run(&root, &["mv", "foo", "bar.desktop"]);
+    assert!(out.status.success(), "{out:?}");
+    assert!(!apps.join("foo.desktop").exists());
+    assert_eq!(fs::read_to_string(apps.join("bar.desktop")).unwrap(), APP);
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(
+        stderr.contains("mimeapps.list:2 still refers to foo.desktop"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("autostart/foo.desktop still starts foo.desktop"),
+        "{stderr}"
+    );
+
+    let out = run(&root, &["mv", "sys", "mine"]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8(out.stderr).unwrap().contains("use cp"));
+
+    fs::remove_dir_all(root).unwrap();
+}
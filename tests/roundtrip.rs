@@ -0,0 +1,79 @@
+mod common;
+
+use common::fixture;
+use deskforge::desktop::{DesktopEntry, Value};
+
+#[test]
+fn untouched_files_are_byte_identical() {
+    for name in [
+        "firefox.desktop",
+        "code.desktop",
+        "htop.desktop",
+        "steam-game.desktop",
+        "link.desktop",
+        "messy.desktop",
+        "crlf.desktop",
+    ] {
+        let original = fixture(name);
+        let entry = DesktopEntry::parse(&original).unwrap();
+        assert_eq!(entry.serialize(), original, "{name}");
+    }
+}
+
+#[test]
+fn setting_the_same_value_keeps_the_original_text() {
+    let original = fixture("messy.desktop");
+    let mut entry = DesktopEntry::parse(&original).unwrap();
+
+    let name = entry.get("Name").cloned().unwrap();
+    entry.set("Name", name);
+    let kind = entry.get("Type").cloned().unwrap();
+    entry.set("Type", kind);
+
+    assert_eq!(entry.serialize(), original);
+}
+
+#[test]
+fn edit_only_touches_changed_keys() {
+    let mut entry = DesktopEntry::parse(&fixture("firefox.desktop")).unwrap();
+
+    entry.set(
+        "Exec",
+        Value::String("env MOZ_ENABLE_WAYLAND=1 firefox %u".into()),
+    );
+    entry.set("NoDisplay", Value::Boolean(true));
+
+    assert_eq!(entry.serialize(), fixture("golden/firefox-edited.desktop"));
+}
+
+#[test]
+fn edit_keeps_comments_unknown_keys_and_groups() {
+    let mut entry = DesktopEntry::parse(&fixture("messy.desktop")).unwrap();
+
+    entry.set(
+        "Categories",
+        Value::List(vec!["Utility".into(), "Development".into()]),
+    );
+    entry.set("Terminal", Value::Boolean(true));
+
+    let action = entry.group_or_insert("Desktop Action edit");
+    action.set("Name", Value::String("Edit".into()));
+    action.set("Exec", Value::String("messy --edit".into()));
+
+    assert_eq!(entry.serialize(), fixture("golden/messy-edited.desktop"));
+}
+
+#[test]
+fn new_entry_serializes_cleanly() {
+    let mut entry = DesktopEntry::new();
+    entry.set("Type", Value::String("Application".into()));
+    entry.set("Name", Value::String(" Leading space".into()));
+    entry
+        .group_or_insert("Desktop Action a")
+        .set("Name", Value::String("A".into()));
+
+    assert_eq!(
+        entry.serialize(),
+        "[Desktop Entry]\nType=Application\nName=\\sLeading space\n\n[Desktop Action a]\nName=A\n"
+    );
+}
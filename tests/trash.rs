@@ -0,0 +1,62 @@
+use deskforge::trash::{Trash, decode_path, encode_path};
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+#[test]
+fn put_writes_trashinfo_and_restore_moves_back() {
+    let root = std::env::temp_dir().join(format!("deskforge-trash-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let apps = root.join("my apps");
+    fs::create_dir_all(&apps).unwrap();
+    let trash = Trash::new(root.join("Trash"));
+
+    let path = apps.join("app.desktop");
+    fs::write(&path, "first").unwrap();
+    let first = trash.put(&path).unwrap();
+    assert!(!path.exists());
+    assert_eq!(first.name, "app.desktop");
+    for dir in ["Trash", "Trash/files", "Trash/info"] {
+        let mode = fs::metadata(root.join(dir)).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700, "{dir}");
+    }
+
+    let info = fs::read_to_string(root.join("Trash/info/app.desktop.trashinfo")).unwrap();
+    let mut lines = info.lines();
+    assert_eq!(lines.next(), Some("[Trash Info]"));
+    assert_eq!(
+        lines.next().unwrap(),
+        format!("Path={}", encode_path(&path))
+    );
+    assert!(info.contains("my%20apps/app.desktop"));
+    let date = lines.next().unwrap().strip_prefix("DeletionDate=").unwrap();
+    assert_eq!(date.len(), "2004-08-31T22:32:08".len());
+    assert_eq!(&date[10..11], "T");
+
+    fs::write(&path, "second").unwrap();
+    let second = trash.put(&path).unwrap();
+    assert_eq!(second.name, "app.2.desktop");
+    assert_eq!(trash.list().len(), 2);
+
+    // A file in the way is never replaced.
+    fs::write(&path, "new").unwrap();
+    assert!(trash.restore(&second).is_err());
+    fs::remove_file(&path).unwrap();
+
+    trash.restore(&second).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+    assert!(!root.join("Trash/info/app.2.desktop.trashinfo").exists());
+    assert_eq!(trash.list(), vec![first]);
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn path_encoding_roundtrip() {
+    let path = Path::new("/home/me/My Apps/ä%.desktop");
+    let encoded = encode_path(path);
+    assert_eq!(encoded, "/home/me/My%20Apps/%C3%A4%25.desktop");
+    assert_eq!(decode_path(&encoded).unwrap(), path);
+    assert_eq!(decode_path("/bad%2"), None);
+}